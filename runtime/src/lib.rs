@@ -0,0 +1,42 @@
+//! Types and traits used by the code which `structout::generate!` outputs.
+
+/// Describes a field of a generated struct, as found in its `FIELDS` constant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FieldInfo {
+    /// The name of the field, or its index for unnamed fields
+    pub name: &'static str,
+    /// The field's type as written in the definition
+    pub ty: &'static str,
+    /// The field's attributes as written in the definition
+    pub attrs: &'static [&'static str],
+}
+
+/// Receives the fields of a struct implementing [`VisitFields`].
+pub trait Visitor {
+    fn field(&mut self, name: &'static str, value: &dyn core::fmt::Debug);
+}
+
+/// Receives the fields of a struct implementing [`VisitFieldsMut`]; the values can be modified
+/// after downcasting them to their concrete types.
+pub trait VisitorMut {
+    fn field(&mut self, name: &'static str, value: &mut dyn core::any::Any);
+}
+
+/// Calls the visitor with each field of the struct, in order.
+pub trait VisitFields {
+    fn visit_fields(&self, visitor: &mut dyn Visitor);
+}
+
+/// Calls the visitor with a mutable reference to each field of the struct, in order.
+pub trait VisitFieldsMut {
+    fn visit_fields_mut(&mut self, visitor: &mut dyn VisitorMut);
+}
+
+/// Gives access to the field marked by `F`, where the markers are the zero-sized types generated
+/// through `#[structout(has_field)]`, e.g. `fields::name`.
+pub trait HasField<F> {
+    type Type;
+
+    fn get(&self) -> &Self::Type;
+    fn get_mut(&mut self) -> &mut Self::Type;
+}
@@ -1,20 +1,21 @@
-#![allow(clippy::eval_order_dependence)]
+#![allow(clippy::mixed_read_write_in_expression)]
 extern crate proc_macro;
 
 // LinkedHashSet is used instead of HashSet in order to insertion order across the board
 use linked_hash_set::LinkedHashSet;
 use proc_macro::TokenStream;
-use proc_macro2::Span;
+use proc_macro2::{Delimiter, Spacing, Span, TokenTree};
 use quote::quote;
 use std::iter::FromIterator;
 use syn::visit::Visit;
 use syn::{
     braced, bracketed, parenthesized,
     parse::{Parse, ParseStream},
-    parse_macro_input,
+    parse_macro_input, parse_quote,
     punctuated::Punctuated,
-    token, Attribute, Field, GenericArgument, Ident, Result, Token, Type, Visibility, WhereClause,
-    WherePredicate,
+    token, Attribute, Expr, ExprField, Field, GenericArgument, Ident, ImplItem, Lit, LitStr,
+    Member, Meta, NestedMeta, Path, PathArguments, Result, Token, Type, Variant, Visibility,
+    WhereClause, WherePredicate,
 };
 
 #[derive(Default)]
@@ -33,8 +34,8 @@ struct TypeArgumentsCheckVisitor<'ast> {
     matched: Vec<&'ast TypeArgumentConfiguration<'ast>>,
 }
 
-impl<'ast> Visit<'ast> for TypeArgumentsCheckVisitor<'ast> {
-    fn visit_ident(&mut self, id: &'ast Ident) {
+impl<'ast, 'a> Visit<'a> for TypeArgumentsCheckVisitor<'ast> {
+    fn visit_ident(&mut self, id: &'a Ident) {
         let name = &id.to_string();
         for arg in self.args.iter() {
             for id in arg.identifiers.iter() {
@@ -46,6 +47,68 @@ impl<'ast> Visit<'ast> for TypeArgumentsCheckVisitor<'ast> {
     }
 }
 
+// Collects the fields accessed through `self.field` (or `self.0`)
+#[derive(Default)]
+struct SelfFieldsVisitor {
+    fields: Vec<Member>,
+}
+
+impl<'ast> Visit<'ast> for SelfFieldsVisitor {
+    fn visit_expr_field(&mut self, expr: &'ast ExprField) {
+        if let Expr::Path(path) = &*expr.base {
+            if path.path.is_ident("self") {
+                self.fields.push(expr.member.clone());
+            }
+        }
+        syn::visit::visit_expr_field(self, expr);
+    }
+
+    // the arguments of macros such as `format!` are expressions as well, while the bodies of other
+    // macros such as `vec![self.a; 3]` are scanned for `self.field` tokens
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        match mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated) {
+            Ok(args) => {
+                for arg in args.iter() {
+                    self.visit_expr(arg);
+                }
+            }
+            Err(_) => self.scan_tokens(mac.tokens.clone()),
+        }
+    }
+}
+
+impl SelfFieldsVisitor {
+    // Collects the `self.field` sequences among tokens which don't parse as expressions
+    fn scan_tokens(&mut self, tokens: proc_macro2::TokenStream) {
+        let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+        for (i, token) in tokens.iter().enumerate() {
+            match token {
+                TokenTree::Group(group) => self.scan_tokens(group.stream()),
+                TokenTree::Ident(ident) if ident == "self" => {
+                    let is_dot = matches!(
+                        tokens.get(i + 1),
+                        Some(TokenTree::Punct(dot)) if dot.as_char() == '.' && dot.spacing() == Spacing::Alone
+                    );
+                    // `self.method()` and `self.method::<T>()` are calls rather than fields
+                    let is_call = match tokens.get(i + 3) {
+                        Some(TokenTree::Group(group)) => {
+                            group.delimiter() == Delimiter::Parenthesis
+                        }
+                        Some(TokenTree::Punct(punct)) => punct.as_char() == ':',
+                        _ => false,
+                    };
+                    if let (true, false, Some(member)) = (is_dot, is_call, tokens.get(i + 2)) {
+                        if let Ok(member) = syn::parse2::<Member>(member.clone().into()) {
+                            self.fields.push(member);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
 struct Generics {
     #[allow(dead_code)]
     start: Token![<],
@@ -80,14 +143,298 @@ impl Parse for Generics {
 }
 
 enum ActionVariant {
-    Omit(Punctuated<Ident, Token![,]>),
-    Include(Punctuated<Ident, Token![,]>),
+    Omit(Punctuated<Member, Token![,]>),
+    Include(Punctuated<Member, Token![,]>),
+    OmitType(Punctuated<Type, Token![,]>),
+    IncludeType(Punctuated<Type, Token![,]>),
+    OmitTagged(Punctuated<Ident, Token![,]>),
+    IncludeTagged(Punctuated<Ident, Token![,]>),
     Attr(Punctuated<Attribute, Token![,]>),
-    Upsert(Punctuated<Field, Token![,]>),
+    Upsert(Punctuated<UpsertItem, Token![,]>),
     AsTuple,
+    AsUnit,
+    AsNamed(Punctuated<Ident, Token![,]>),
+    AsFieldEnum,
+    AsFieldNameEnum,
+    FieldInfo,
+    VisitFields,
+    CommonTrait(CommonTrait),
+    AttrIf(AttrIf),
+    FieldAttr(FieldAttr),
+    FieldAttrIf(FieldAttrIf),
+    StripFieldAttrs(Punctuated<Path, Token![,]>),
+    NoInherit,
+    StripAttr(Punctuated<Meta, Token![,]>),
+    Derive(Punctuated<Path, Token![,]>),
+    Underive(Punctuated<Path, Token![,]>),
+    Doc(LitStr),
+    Cfg(proc_macro2::TokenStream),
+    Vis(Visibility),
+    FieldVis(FieldVisibility),
+}
+
+struct FieldVisibility {
+    visibility: Visibility,
+    // applies to all fields if empty
+    fields: Punctuated<Member, Token![,]>,
+}
+
+impl Parse for FieldVisibility {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(FieldVisibility {
+            visibility: input.parse()?,
+            fields: {
+                if input.is_empty() {
+                    Punctuated::new()
+                } else {
+                    input.parse::<Token![,]>()?;
+                    input.parse_terminated(Member::parse)?
+                }
+            },
+        })
+    }
+}
+
+struct CommonTrait {
+    name: Ident,
+    // generates setters along with the getters if present
+    setters: Option<Ident>,
+}
+
+impl Parse for CommonTrait {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(CommonTrait {
+            name: input.parse()?,
+            setters: {
+                if input.is_empty() {
+                    None
+                } else {
+                    input.parse::<Token![,]>()?;
+                    let setters: Ident = input.parse()?;
+                    if setters != "setters" {
+                        return Err(syn::Error::new_spanned(setters, "expected `setters`"));
+                    }
+                    Some(setters)
+                }
+            },
+        })
+    }
+}
+
+// A condition on the items (fields or variants) of an output, e.g. `all(has(id), not(has(name)))`
+enum Condition {
+    Has(Punctuated<Member, Token![,]>),
+    Not(Box<Condition>),
+    All(Punctuated<Condition, Token![,]>),
+    Any(Punctuated<Condition, Token![,]>),
+}
+
+impl Parse for Condition {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        let name: Ident = input.parse()?;
+        parenthesized!(content in input);
+
+        if name == "has" {
+            Ok(Condition::Has(content.parse_terminated(Member::parse)?))
+        } else if name == "not" {
+            Ok(Condition::Not(Box::new(content.parse()?)))
+        } else if name == "all" {
+            Ok(Condition::All(content.parse_terminated(Condition::parse)?))
+        } else if name == "any" {
+            Ok(Condition::Any(content.parse_terminated(Condition::parse)?))
+        } else {
+            Err(syn::Error::new_spanned(
+                &name,
+                format!("{} is not a valid condition", name),
+            ))
+        }
+    }
+}
+
+impl Condition {
+    fn holds(&self, item_names: &[String]) -> bool {
+        match self {
+            Condition::Has(members) => members
+                .iter()
+                .all(|member| item_names.contains(&member_name(member))),
+            Condition::Not(condition) => !condition.holds(item_names),
+            Condition::All(conditions) => conditions.iter().all(|c| c.holds(item_names)),
+            Condition::Any(conditions) => conditions.iter().any(|c| c.holds(item_names)),
+        }
+    }
+}
+
+struct AttrIf {
+    condition: Condition,
+    attrs: Vec<Attribute>,
+}
+
+impl Parse for AttrIf {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(AttrIf {
+            condition: input.parse()?,
+            attrs: {
+                input.parse::<Token![,]>()?;
+                input.call(Attribute::parse_outer)?
+            },
+        })
+    }
+}
+
+struct FieldAttr {
+    // applies to all fields if absent
+    field: Option<Member>,
+    attrs: Vec<Attribute>,
+}
+
+impl FieldAttr {
+    fn parse_for_field(input: ParseStream) -> Result<Self> {
+        Ok(FieldAttr {
+            field: {
+                let field = input.parse()?;
+                input.parse::<Token![,]>()?;
+                Some(field)
+            },
+            attrs: input.call(Attribute::parse_outer)?,
+        })
+    }
+
+    fn parse_for_all(input: ParseStream) -> Result<Self> {
+        Ok(FieldAttr {
+            field: None,
+            attrs: input.call(Attribute::parse_outer)?,
+        })
+    }
+}
+
+struct FieldAttrIf {
+    condition: Condition,
+    field: Member,
+    attrs: Vec<Attribute>,
+}
+
+impl Parse for FieldAttrIf {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(FieldAttrIf {
+            condition: input.parse()?,
+            field: {
+                input.parse::<Token![,]>()?;
+                input.parse()?
+            },
+            attrs: {
+                input.parse::<Token![,]>()?;
+                input.call(Attribute::parse_outer)?
+            },
+        })
+    }
+}
+
+// An item of the base's body: either a field of a struct or a variant of an enum
+#[derive(Clone)]
+enum Item {
+    Field(Field),
+    Variant(Variant),
+}
+
+impl Item {
+    fn types(&self) -> Vec<&Type> {
+        match self {
+            Item::Field(field) => vec![&field.ty],
+            Item::Variant(variant) => variant.fields.iter().map(|f| &f.ty).collect(),
+        }
+    }
+
+    fn attrs(&self) -> &Vec<Attribute> {
+        match self {
+            Item::Field(field) => &field.attrs,
+            Item::Variant(variant) => &variant.attrs,
+        }
+    }
+
+    fn attrs_mut(&mut self) -> &mut Vec<Attribute> {
+        match self {
+            Item::Field(field) => &mut field.attrs,
+            Item::Variant(variant) => &mut variant.attrs,
+        }
+    }
+}
+
+// Fields are addressed by name for named bases or by index for tuple bases, e.g. `foo: u32` or
+// `0: u32`, while variants are declared as in an enum, e.g. `Foo(u32)`
+struct UpsertItem {
+    member: Member,
+    item: Item,
+}
+
+impl Parse for UpsertItem {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let fork = input.fork();
+        fork.call(Attribute::parse_outer)?;
+        fork.parse::<Visibility>()?;
+        fork.parse::<Member>()?;
+        if !fork.peek(Token![:]) {
+            let variant: Variant = input.parse()?;
+            return Ok(UpsertItem {
+                member: Member::Named(variant.ident.clone()),
+                item: Item::Variant(variant),
+            });
+        }
+
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let member: Member = input.parse()?;
+        let colon_token = input.parse()?;
+        let ty = input.parse()?;
+
+        Ok(UpsertItem {
+            item: Item::Field(Field {
+                attrs,
+                vis,
+                ident: match &member {
+                    Member::Named(ident) => Some(ident.clone()),
+                    Member::Unnamed(_) => None,
+                },
+                colon_token: match &member {
+                    Member::Named(_) => Some(colon_token),
+                    Member::Unnamed(_) => None,
+                },
+                ty,
+            }),
+            member,
+        })
+    }
+}
+
+// The name of a generic argument without its bounds, e.g. `T` for `T: Clone`
+fn generic_name(arg: &GenericArgument) -> proc_macro2::TokenStream {
+    match arg {
+        GenericArgument::Constraint(constraint) => {
+            let ident = &constraint.ident;
+            quote! { #ident }
+        }
+        arg => quote! { #arg },
+    }
+}
+
+// The setter for a field, e.g. `set_name` for `name`
+fn setter_ident(field: &Ident) -> Ident {
+    let name = field.to_string();
+    Ident::new(
+        &format!("set_{}", name.trim_start_matches("r#")),
+        field.span(),
+    )
+}
+
+fn member_name(member: &Member) -> String {
+    match member {
+        Member::Named(ident) => ident.to_string(),
+        Member::Unnamed(index) => index.index.to_string(),
+    }
 }
 
 struct Action {
+    name: Ident,
     #[allow(dead_code)]
     parens: token::Paren,
     fields: ActionVariant,
@@ -100,19 +447,68 @@ impl Parse for Action {
         let name_str = &name.to_string();
 
         Ok(Action {
+            name: name.clone(),
             parens: parenthesized!(content in input),
             fields: {
                 if name_str == "omit" {
-                    ActionVariant::Omit(content.parse_terminated(Ident::parse)?)
+                    ActionVariant::Omit(content.parse_terminated(Member::parse)?)
                 } else if name_str == "include" {
-                    ActionVariant::Include(content.parse_terminated(Ident::parse)?)
+                    ActionVariant::Include(content.parse_terminated(Member::parse)?)
+                } else if name_str == "omit_type" {
+                    ActionVariant::OmitType(content.parse_terminated(Type::parse)?)
+                } else if name_str == "include_type" {
+                    ActionVariant::IncludeType(content.parse_terminated(Type::parse)?)
+                } else if name_str == "omit_tagged" {
+                    ActionVariant::OmitTagged(content.parse_terminated(Ident::parse)?)
+                } else if name_str == "include_tagged" {
+                    ActionVariant::IncludeTagged(content.parse_terminated(Ident::parse)?)
                 } else if name_str == "as_tuple" {
                     ActionVariant::AsTuple
+                } else if name_str == "as_unit" {
+                    ActionVariant::AsUnit
+                } else if name_str == "as_field_enum" {
+                    ActionVariant::AsFieldEnum
+                } else if name_str == "as_field_name_enum" {
+                    ActionVariant::AsFieldNameEnum
+                } else if name_str == "field_info" {
+                    ActionVariant::FieldInfo
+                } else if name_str == "visit_fields" {
+                    ActionVariant::VisitFields
+                } else if name_str == "common_trait" {
+                    ActionVariant::CommonTrait(content.parse()?)
+                } else if name_str == "attr_if" {
+                    ActionVariant::AttrIf(content.parse()?)
+                } else if name_str == "field_attr_if" {
+                    ActionVariant::FieldAttrIf(content.parse()?)
+                } else if name_str == "field_attr" {
+                    ActionVariant::FieldAttr(FieldAttr::parse_for_field(&content)?)
+                } else if name_str == "field_attr_all" {
+                    ActionVariant::FieldAttr(FieldAttr::parse_for_all(&content)?)
+                } else if name_str == "strip_field_attrs" {
+                    ActionVariant::StripFieldAttrs(content.parse_terminated(Path::parse)?)
+                } else if name_str == "no_inherit" {
+                    ActionVariant::NoInherit
+                } else if name_str == "strip_attr" {
+                    ActionVariant::StripAttr(content.parse_terminated(Meta::parse)?)
+                } else if name_str == "derive" {
+                    ActionVariant::Derive(content.parse_terminated(Path::parse)?)
+                } else if name_str == "underive" {
+                    ActionVariant::Underive(content.parse_terminated(Path::parse)?)
+                } else if name_str == "doc" {
+                    ActionVariant::Doc(content.parse()?)
+                } else if name_str == "cfg" {
+                    ActionVariant::Cfg(content.parse()?)
+                } else if name_str == "as_named" {
+                    ActionVariant::AsNamed(content.parse_terminated(Ident::parse)?)
                 } else if name_str == "attr" {
                     use syn::parse_quote::ParseQuote;
                     ActionVariant::Attr(content.parse_terminated(Attribute::parse)?)
                 } else if name_str == "upsert" {
-                    ActionVariant::Upsert(content.parse_terminated(Field::parse_named)?)
+                    ActionVariant::Upsert(content.parse_terminated(UpsertItem::parse)?)
+                } else if name_str == "vis" {
+                    ActionVariant::Vis(content.parse()?)
+                } else if name_str == "field_vis" {
+                    ActionVariant::FieldVis(content.parse()?)
                 } else {
                     panic!("{} is not a valid action", name_str)
                 }
@@ -121,8 +517,52 @@ impl Parse for Action {
     }
 }
 
+// The name of a generated struct, either given as is or through a template such as
+// `"{Base}Create"`, where `{Base}` is replaced by the name of the base
+enum StructName {
+    Ident(Ident),
+    Template(LitStr),
+}
+
+impl Parse for StructName {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(LitStr) {
+            Ok(StructName::Template(input.parse()?))
+        } else {
+            Ok(StructName::Ident(input.parse()?))
+        }
+    }
+}
+
+impl StructName {
+    fn resolve(&self, base_name: Option<&Ident>) -> Result<Ident> {
+        let template = match self {
+            StructName::Ident(ident) => return Ok(ident.clone()),
+            StructName::Template(template) => template,
+        };
+        let base_name = match base_name {
+            Some(base_name) => base_name,
+            None => {
+                return Err(syn::Error::new_spanned(
+                    template,
+                    "naming templates need a named base",
+                ))
+            }
+        };
+        let name = template.value().replace("{Base}", &base_name.to_string());
+        syn::parse_str::<Ident>(&name)
+            .map(|mut ident| {
+                ident.set_span(template.span());
+                ident
+            })
+            .map_err(|_| {
+                syn::Error::new_spanned(template, format!("{} is not a valid struct name", name))
+            })
+    }
+}
+
 struct ConfigurationExpr {
-    struct_name: Ident,
+    struct_name: StructName,
     #[allow(dead_code)]
     arrow: Token![=>],
     #[allow(dead_code)]
@@ -143,485 +583,6286 @@ impl Parse for ConfigurationExpr {
     }
 }
 
+// Either a struct to generate or the structs of a preset, e.g. `..Crud`
+enum ConfigurationEntry {
+    Struct(ConfigurationExpr),
+    Preset(#[allow(dead_code)] Token![..], Ident),
+}
+
+impl Parse for ConfigurationEntry {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Token![..]) {
+            Ok(ConfigurationEntry::Preset(input.parse()?, input.parse()?))
+        } else {
+            Ok(ConfigurationEntry::Struct(input.parse()?))
+        }
+    }
+}
+
+// A reusable list of structs to generate, which can be applied to any of the definitions that
+// follow it, e.g. `preset Crud { "{Base}Create" => [omit(id)], "{Base}View" => [] }`
+struct Preset {
+    name: Ident,
+    #[allow(dead_code)]
+    brace: token::Brace,
+    conf: Punctuated<ConfigurationEntry, Token![,]>,
+}
+
+impl Parse for Preset {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let conf_content;
+
+        let keyword: Ident = input.parse()?;
+        if keyword != "preset" {
+            return Err(syn::Error::new_spanned(keyword, "expected `preset`"));
+        }
+
+        Ok(Preset {
+            name: input.parse()?,
+            brace: braced!(conf_content in input),
+            conf: conf_content.parse_terminated(ConfigurationEntry::parse)?,
+        })
+    }
+}
+
+// Named fields which can be added to the body of any of the definitions that follow it, e.g.
+// `mixin Timestamped { created_at: u64, updated_at: u64 }`
+struct Mixin {
+    name: Ident,
+    #[allow(dead_code)]
+    brace: token::Brace,
+    fields: Punctuated<Field, Token![,]>,
+}
+
+impl Parse for Mixin {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let fields_content;
+
+        let keyword: Ident = input.parse()?;
+        if keyword != "mixin" {
+            return Err(syn::Error::new_spanned(keyword, "expected `mixin`"));
+        }
+
+        Ok(Mixin {
+            name: input.parse()?,
+            brace: braced!(fields_content in input),
+            fields: fields_content.parse_terminated(Field::parse_named)?,
+        })
+    }
+}
+
+// What can be defined ahead of the definitions, either within the invocation or through
+// `template!`
+enum TemplateItem {
+    Preset(Preset),
+    Mixin(Mixin),
+}
+
+impl Parse for TemplateItem {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let keyword: Ident = input.fork().parse()?;
+        if keyword == "mixin" {
+            Ok(TemplateItem::Mixin(input.parse()?))
+        } else {
+            Ok(TemplateItem::Preset(input.parse()?))
+        }
+    }
+}
+
+impl TemplateItem {
+    fn name(&self) -> &Ident {
+        match self {
+            TemplateItem::Preset(preset) => &preset.name,
+            TemplateItem::Mixin(mixin) => &mixin.name,
+        }
+    }
+
+    // whether a template item starts at the input, i.e. `preset Name` or `mixin Name`
+    fn peek(input: ParseStream) -> bool {
+        let fork = input.fork();
+        matches!(fork.parse::<Ident>(), Ok(ident) if ident == "preset" || ident == "mixin")
+            && fork.peek(Ident)
+    }
+}
+
+// The structs of the given entries, with the presets they refer to spread in place
+fn resolve_configuration<'a>(
+    entries: &'a Punctuated<ConfigurationEntry, Token![,]>,
+    templates: &'a [TemplateItem],
+) -> Result<Vec<&'a ConfigurationExpr>> {
+    let mut conf = Vec::new();
+    for entry in entries.iter() {
+        match entry {
+            ConfigurationEntry::Struct(c) => conf.push(c),
+            ConfigurationEntry::Preset(_, name) => {
+                // presets can only refer to the ones defined before them
+                let preset = templates.iter().enumerate().find_map(|(i, t)| match t {
+                    TemplateItem::Preset(preset) if &preset.name == name => Some((i, preset)),
+                    _ => None,
+                });
+                let (position, preset) = match preset {
+                    Some(preset) => preset,
+                    None => {
+                        return Err(syn::Error::new_spanned(
+                            name,
+                            format!("{} is not a preset defined before this", name),
+                        ))
+                    }
+                };
+                conf.extend(resolve_configuration(&preset.conf, &templates[..position])?);
+            }
+        }
+    }
+    Ok(conf)
+}
+
+// The fields of a named body followed by the ones it's extended with, in order
+fn resolve_fields(
+    fields: &Punctuated<Field, Token![,]>,
+    extensions: &[BodyExtension],
+    templates: &[TemplateItem],
+) -> Result<Vec<Field>> {
+    let mut resolved: Vec<Field> = fields.iter().cloned().collect();
+    for extension in extensions.iter() {
+        match extension {
+            BodyExtension::Fields { fields, .. } => resolved.extend(fields.iter().cloned()),
+            BodyExtension::Mixins { names, .. } => {
+                for name in names.iter() {
+                    let mixin = templates.iter().find_map(|t| match t {
+                        TemplateItem::Mixin(mixin) if &mixin.name == name => Some(mixin),
+                        _ => None,
+                    });
+                    match mixin {
+                        Some(mixin) => resolved.extend(mixin.fields.iter().cloned()),
+                        None => {
+                            return Err(syn::Error::new_spanned(
+                                name,
+                                format!("{} is not a mixin defined before this", name),
+                            ))
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    for (i, field) in resolved.iter().enumerate() {
+        let ident = field.ident.as_ref().unwrap();
+        if resolved[..i]
+            .iter()
+            .any(|f| f.ident.as_ref() == Some(ident))
+        {
+            return Err(syn::Error::new_spanned(
+                ident,
+                format!("the field {} is defined more than once", ident),
+            ));
+        }
+    }
+    Ok(resolved)
+}
+
+// The contents of a `generate!` invocation: any number of presets, mixins and definitions
+struct Definitions {
+    templates: Vec<TemplateItem>,
+    // along with the number of templates defined before each of them
+    definitions: Vec<(StructGen, usize)>,
+}
+
+impl Parse for Definitions {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut templates = Vec::new();
+        let mut definitions = Vec::new();
+        while !input.is_empty() {
+            if TemplateItem::peek(input) {
+                templates.push(input.parse()?);
+            } else {
+                definitions.push((input.parse()?, templates.len()));
+            }
+        }
+
+        Ok(Definitions {
+            templates,
+            definitions,
+        })
+    }
+}
+
 struct StructGen {
     attrs: Vec<Attribute>,
     visibility: Option<Visibility>,
+    #[allow(dead_code)]
+    struct_token: Option<Token![struct]>,
+    #[allow(dead_code)]
+    enum_token: Option<Token![enum]>,
+    // the base is output as well when it's named
+    name: Option<Ident>,
     generics: Option<Generics>,
     where_clause: Option<WhereClause>,
-    #[allow(dead_code)]
-    brace: token::Brace,
-    fields: Punctuated<Field, Token![,]>,
+    body: StructBody,
     #[allow(dead_code)]
     arrow: token::FatArrow,
     #[allow(dead_code)]
     conf_brace: token::Brace,
-    conf: Punctuated<ConfigurationExpr, Token![,]>,
+    conf: Punctuated<ConfigurationEntry, Token![,]>,
+    impls: Vec<ImplTemplate>,
 }
 
-impl Parse for StructGen {
+// A block of items which is implemented for the structs that have the fields it uses
+struct ImplTemplate {
+    #[allow(dead_code)]
+    impl_token: Token![impl],
+    // all structs are targeted if no targets are given
+    targets: Option<Punctuated<Ident, Token![,]>>,
+    #[allow(dead_code)]
+    brace: token::Brace,
+    items: Vec<ImplItem>,
+}
+
+impl Parse for ImplTemplate {
     fn parse(input: ParseStream) -> Result<Self> {
-        let struct_content;
-        let conf_content;
+        let items_content;
 
-        Ok(StructGen {
-            attrs: input.call(Attribute::parse_outer)?,
-            visibility: {
-                if input.lookahead1().peek(Token![pub]) {
-                    Some(input.parse()?)
-                } else {
-                    None
-                }
-            },
-            generics: {
-                if input.lookahead1().peek(Token![<]) {
-                    Some(input.parse()?)
+        Ok(ImplTemplate {
+            impl_token: input.parse()?,
+            targets: {
+                if input.peek(Token![for]) {
+                    input.parse::<Token![for]>()?;
+                    Some(Punctuated::parse_separated_nonempty(input)?)
                 } else {
                     None
                 }
             },
-            where_clause: {
-                if input.lookahead1().peek(Token![where]) {
-                    Some(input.parse()?)
-                } else {
-                    None
+            brace: braced!(items_content in input),
+            items: {
+                let mut items = Vec::new();
+                while !items_content.is_empty() {
+                    items.push(items_content.parse()?);
                 }
+                items
             },
-            brace: braced!(struct_content in input),
-            fields: struct_content.parse_terminated(Field::parse_named)?,
-            arrow: input.parse()?,
-            conf_brace: braced!(conf_content in input),
-            conf: conf_content.parse_terminated(ConfigurationExpr::parse)?,
         })
     }
 }
 
-struct StructOutputConfiguration<'ast> {
-    omitted_fields: LinkedHashSet<String>,
-    included_fields: LinkedHashSet<String>,
-    upsert_fields_names: LinkedHashSet<String>,
-    upsert_fields: Vec<&'ast Field>,
-    attributes: Vec<&'ast Attribute>,
-    is_tuple: bool,
+// More fields for a named body, e.g. `+ { deleted: bool }` or `+ mixin(Identified, Timestamped)`
+enum BodyExtension {
+    Fields {
+        #[allow(dead_code)]
+        plus: Token![+],
+        #[allow(dead_code)]
+        brace: token::Brace,
+        fields: Punctuated<Field, Token![,]>,
+    },
+    Mixins {
+        #[allow(dead_code)]
+        plus: Token![+],
+        #[allow(dead_code)]
+        mixin: Ident,
+        #[allow(dead_code)]
+        paren: token::Paren,
+        names: Punctuated<Ident, Token![,]>,
+    },
 }
 
-struct TypeArgumentConfiguration<'ast> {
-    arg: &'ast GenericArgument,
-    identifiers: LinkedHashSet<String>,
-}
+impl Parse for BodyExtension {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
 
-#[proc_macro]
-pub fn generate(input: TokenStream) -> TokenStream {
-    let StructGen {
-        attrs: top_level_attrs,
-        generics: parsed_generics,
-        where_clause,
-        fields: parsed_fields,
-        conf,
-        visibility,
-        ..
-    } = parse_macro_input!(input as StructGen);
+        let plus = input.parse()?;
+        if input.peek(token::Brace) {
+            Ok(BodyExtension::Fields {
+                plus,
+                brace: braced!(content in input),
+                fields: content.parse_terminated(Field::parse_named)?,
+            })
+        } else {
+            let mixin: Ident = input.parse()?;
+            if mixin != "mixin" {
+                return Err(syn::Error::new_spanned(
+                    mixin,
+                    "expected a struct body or `mixin(...)`",
+                ));
+            }
+            Ok(BodyExtension::Mixins {
+                plus,
+                mixin,
+                paren: parenthesized!(content in input),
+                names: content.parse_terminated(Ident::parse)?,
+            })
+        }
+    }
+}
 
-    let structs: Vec<(String, StructOutputConfiguration)> = conf
-        .iter()
-        .map(|c| {
-            let mut omitted_fields = LinkedHashSet::<String>::new();
-            let mut included_fields = LinkedHashSet::<String>::new();
-            let mut upsert_fields = Vec::<&Field>::new();
-            let mut upsert_fields_names = LinkedHashSet::<String>::new();
-            let mut attributes = Vec::<&Attribute>::new();
-            attributes.extend(top_level_attrs.iter());
-            let mut is_tuple = false;
+enum StructBody {
+    Named {
+        #[allow(dead_code)]
+        brace: token::Brace,
+        fields: Punctuated<Field, Token![,]>,
+        extensions: Vec<BodyExtension>,
+    },
+    // as in tuple struct declarations, the where clause comes after the fields
+    Tuple {
+        #[allow(dead_code)]
+        paren: token::Paren,
+        fields: Punctuated<Field, Token![,]>,
+        where_clause: Option<WhereClause>,
+    },
+    Enum {
+        #[allow(dead_code)]
+        brace: token::Brace,
+        variants: Punctuated<Variant, Token![,]>,
+    },
+    // the fields are read from a JSON Schema file, relative to the crate's manifest
+    File {
+        #[allow(dead_code)]
+        from_file: Ident,
+        #[allow(dead_code)]
+        eq: Token![=],
+        path: LitStr,
+    },
+}
 
-            for a in c.actions.iter() {
-                match &a.fields {
-                    ActionVariant::Omit(fields) => {
-                        omitted_fields.extend(fields.iter().map(|f| f.to_string()));
-                    }
-                    ActionVariant::Include(fields) => {
-                        included_fields.extend(fields.iter().map(|f| f.to_string()));
-                    }
-                    ActionVariant::Attr(attrs) => {
-                        attributes.extend(attrs.iter());
-                    }
-                    ActionVariant::Upsert(fields) => {
-                        upsert_fields_names
-                            .extend(fields.iter().map(|f| f.ident.as_ref().unwrap().to_string()));
-                        upsert_fields.extend(fields);
+impl StructBody {
+    fn parse_enum(input: ParseStream) -> Result<Self> {
+        let enum_content;
+
+        Ok(StructBody::Enum {
+            brace: braced!(enum_content in input),
+            variants: enum_content.parse_terminated(Variant::parse)?,
+        })
+    }
+}
+
+impl Parse for StructBody {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let struct_content;
+
+        if input.peek(Ident) {
+            let from_file: Ident = input.parse()?;
+            if from_file != "from_file" {
+                return Err(syn::Error::new_spanned(
+                    from_file,
+                    "expected a struct body or `from_file = \"path\"`",
+                ));
+            }
+            Ok(StructBody::File {
+                from_file,
+                eq: input.parse()?,
+                path: input.parse()?,
+            })
+        } else if input.peek(token::Paren) {
+            Ok(StructBody::Tuple {
+                paren: parenthesized!(struct_content in input),
+                fields: struct_content.parse_terminated(Field::parse_unnamed)?,
+                where_clause: {
+                    if input.lookahead1().peek(Token![where]) {
+                        Some(input.parse()?)
+                    } else {
+                        None
                     }
-                    ActionVariant::AsTuple => {
-                        is_tuple = true;
+                },
+            })
+        } else {
+            Ok(StructBody::Named {
+                brace: braced!(struct_content in input),
+                fields: struct_content.parse_terminated(Field::parse_named)?,
+                extensions: {
+                    let mut extensions = Vec::new();
+                    while input.peek(Token![+]) {
+                        extensions.push(input.parse()?);
                     }
+                    extensions
+                },
+            })
+        }
+    }
+}
+
+impl Parse for StructGen {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let conf_content;
+
+        let attrs = input.call(Attribute::parse_outer)?;
+        let visibility = if input.lookahead1().peek(Token![pub]) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let struct_token: Option<Token![struct]> = input.parse()?;
+        let enum_token: Option<Token![enum]> = if struct_token.is_none() {
+            input.parse()?
+        } else {
+            None
+        };
+        let name = if (struct_token.is_some() || enum_token.is_some()) && input.peek(Ident) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let generics = if input.lookahead1().peek(Token![<]) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let where_clause = if input.lookahead1().peek(Token![where]) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let body = if enum_token.is_some() {
+            StructBody::parse_enum(input)?
+        } else {
+            input.parse()?
+        };
+
+        Ok(StructGen {
+            attrs,
+            visibility,
+            struct_token,
+            enum_token,
+            name,
+            generics,
+            where_clause,
+            body,
+            arrow: input.parse()?,
+            conf_brace: braced!(conf_content in input),
+            conf: conf_content.parse_terminated(ConfigurationEntry::parse)?,
+            impls: {
+                let mut impls = Vec::new();
+                while input.peek(Token![impl]) {
+                    impls.push(input.parse()?);
                 }
+                impls
+            },
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum StructShape {
+    Named,
+    Tuple,
+    Unit,
+    Enum,
+    // an enum with one variant per field
+    FieldEnum,
+    // a fieldless enum with one variant per field
+    FieldNameEnum,
+}
+
+#[derive(Default)]
+struct StructOutputConfiguration<'ast> {
+    omitted_fields: LinkedHashSet<String>,
+    included_fields: LinkedHashSet<String>,
+    omitted_types: Vec<&'ast Type>,
+    included_types: Vec<&'ast Type>,
+    omitted_tags: LinkedHashSet<String>,
+    included_tags: LinkedHashSet<String>,
+    upsert_fields_names: LinkedHashSet<String>,
+    upsert_fields: Vec<&'ast UpsertItem>,
+    // the top-level attributes, unless they're not inherited
+    inherited_attributes: Vec<&'ast Attribute>,
+    stripped_attributes: Vec<&'ast Meta>,
+    attributes: Vec<&'ast Attribute>,
+    // merged with the derives of the attributes
+    derives: Vec<&'ast Path>,
+    underives: Vec<&'ast Path>,
+    docs: Vec<&'ast LitStr>,
+    cfgs: Vec<&'ast proc_macro2::TokenStream>,
+    // documents how the struct relates to the base
+    auto_doc: bool,
+    // evaluated once the items of the output are known
+    conditional_attributes: Vec<&'ast AttrIf>,
+    conditional_item_attributes: Vec<&'ast FieldAttrIf>,
+    // the inherited attributes are stripped before the given ones are inserted
+    item_attributes: Vec<&'ast FieldAttr>,
+    stripped_item_attributes: Vec<&'ast Path>,
+    // defaults to the shape of the base
+    shape: Option<StructShape>,
+    names: Option<&'ast Punctuated<Ident, Token![,]>>,
+    visibility: Option<&'ast Visibility>,
+    field_visibilities: Vec<&'ast FieldVisibility>,
+    field_info: bool,
+    visit_fields: bool,
+    common_traits: Vec<&'ast CommonTrait>,
+}
+
+impl StructShape {
+    fn description(self) -> &'static str {
+        match self {
+            StructShape::Named => "a struct with named fields",
+            StructShape::Tuple => "a tuple struct",
+            StructShape::Unit => "a unit struct",
+            StructShape::Enum => "an enum",
+            StructShape::FieldEnum => "an enum with one variant per field",
+            StructShape::FieldNameEnum => "a fieldless enum with one variant per field",
+        }
+    }
+}
+
+// The final form of a struct after its actions have been applied
+struct OutputStruct<'ast> {
+    name: Ident,
+    attributes: Vec<Attribute>,
+    visibility: Option<&'ast Visibility>,
+    shape: StructShape,
+    // the fields keep their identifiers regardless of the shape they're output with
+    fields: Vec<Field>,
+    variants: Vec<Variant>,
+    generics: Vec<&'ast GenericArgument>,
+    wheres: Vec<&'ast WherePredicate>,
+    field_info: bool,
+    visit_fields: bool,
+    common_traits: Vec<&'ast CommonTrait>,
+}
+
+impl<'ast> OutputStruct<'ast> {
+    // the generics as they should be declared, e.g. `<T: Copy>`
+    fn impl_generics(&self) -> proc_macro2::TokenStream {
+        let generic_items = &self.generics;
+        if generic_items.is_empty() {
+            quote! {}
+        } else {
+            quote! { <#(#generic_items),*> }
+        }
+    }
+
+    // the generics as they should be used in a type position, e.g. `<T>`
+    fn type_generics(&self) -> proc_macro2::TokenStream {
+        let generic_names = self.generics.iter().copied().map(generic_name);
+        if self.generics.is_empty() {
+            quote! {}
+        } else {
+            quote! { <#(#generic_names),*> }
+        }
+    }
+
+    fn where_clause(&self) -> proc_macro2::TokenStream {
+        let where_items = &self.wheres;
+        if where_items.is_empty() {
+            quote! {}
+        } else {
+            quote! { where #(#where_items),* }
+        }
+    }
+
+    // The where clause extended with the given predicates
+    fn where_clause_with(
+        &self,
+        predicates: &[proc_macro2::TokenStream],
+    ) -> proc_macro2::TokenStream {
+        let where_items = &self.wheres;
+        if where_items.is_empty() && predicates.is_empty() {
+            quote! {}
+        } else {
+            quote! { where #(#where_items,)* #(#predicates),* }
+        }
+    }
+
+    // The types of the fields which depend on the struct's generics
+    fn generic_field_types(&self) -> Vec<&Type> {
+        let mut generics = TypeArgumentsCollectorVisitor::default();
+        for arg in self.generics.iter() {
+            generics.visit_generic_argument(arg);
+        }
+
+        let mut types: Vec<&Type> = Vec::new();
+        for f in self.fields.iter() {
+            let mut collector = TypeArgumentsCollectorVisitor::default();
+            collector.visit_type(&f.ty);
+            if collector.items.iter().any(|id| generics.items.contains(id))
+                && !types.contains(&&f.ty)
+            {
+                types.push(&f.ty);
             }
+        }
+        types
+    }
 
-            (
-                c.struct_name.to_string(),
-                StructOutputConfiguration {
-                    omitted_fields,
-                    included_fields,
-                    upsert_fields,
-                    upsert_fields_names,
-                    attributes,
-                    is_tuple,
-                },
-            )
-        })
-        .collect();
+    // How a field is accessed on a value of this struct, e.g. `name` or `0`
+    fn field_member(&self, index: usize) -> Member {
+        match (&self.fields[index].ident, self.shape) {
+            (Some(ident), StructShape::Named) => Member::Named(ident.clone()),
+            _ => Member::Unnamed(syn::Index::from(index)),
+        }
+    }
 
-    let generics: Vec<TypeArgumentConfiguration> = if parsed_generics.is_some() {
-        parsed_generics
-            .as_ref()
-            .unwrap()
-            .args
-            .iter()
-            .map(|arg| {
-                let mut collector = TypeArgumentsCollectorVisitor {
-                    ..Default::default()
-                };
-                collector.visit_generic_argument(arg);
+    fn field_ident(&self, index: usize) -> &Ident {
+        self.fields[index].ident.as_ref().unwrap()
+    }
 
-                TypeArgumentConfiguration {
-                    arg,
-                    identifiers: collector.items,
-                }
-            })
-            .collect()
-    } else {
-        Vec::new()
-    };
+    // The `#[cfg(...)]` attributes of the struct, which all of its impls carry as well
+    fn cfg(&self) -> proc_macro2::TokenStream {
+        cfg_attributes(&self.attributes)
+    }
 
-    let wheres: Vec<(&WherePredicate, Vec<&TypeArgumentConfiguration>)> = if where_clause.is_some()
-    {
-        where_clause
-            .as_ref()
-            .unwrap()
-            .predicates
-            .iter()
-            .map(|p| {
-                let mut collector = TypeArgumentsCheckVisitor {
-                    args: &generics,
-                    matched: Vec::new(),
-                };
-                collector.visit_where_predicate(&p);
+    fn field_cfg(&self, index: usize) -> proc_macro2::TokenStream {
+        cfg_attributes(&self.fields[index].attrs)
+    }
+
+    // The distinct predicates which the first `count` fields are gated behind
+    fn field_cfg_predicates(&self, count: usize) -> Vec<proc_macro2::TokenStream> {
+        let mut predicates: Vec<proc_macro2::TokenStream> = Vec::new();
+        for predicate in self.fields[..count].iter().flat_map(|f| cfgs(&f.attrs)) {
+            if !predicates
+                .iter()
+                .any(|p| p.to_string() == predicate.to_string())
+            {
+                predicates.push(predicate);
+            }
+        }
+        predicates
+    }
 
-                (p, collector.matched)
+    // Each combination of the predicates of the fields, as the attribute enabling it along with the
+    // indexes of the fields it keeps, for the code which depends on their positions
+    fn field_configurations(&self) -> Vec<(proc_macro2::TokenStream, Vec<usize>)> {
+        let count = self.fields.len();
+        let predicates = self.field_cfg_predicates(count);
+        if predicates.is_empty() {
+            return vec![(quote! {}, (0..count).collect())];
+        }
+
+        (0..(1usize << predicates.len()))
+            .map(|enabled| {
+                let is_enabled = |predicate: &proc_macro2::TokenStream| {
+                    let i = predicates
+                        .iter()
+                        .position(|p| p.to_string() == predicate.to_string())
+                        .unwrap();
+                    enabled & (1 << i) != 0
+                };
+                let configuration = predicates.iter().map(|p| {
+                    if is_enabled(p) {
+                        quote! { #p }
+                    } else {
+                        quote! { not(#p) }
+                    }
+                });
+                let fields = (0..count)
+                    .filter(|i| cfgs(&self.fields[*i].attrs).iter().all(is_enabled))
+                    .collect();
+                (quote! { #[cfg(all(#(#configuration),*))] }, fields)
             })
             .collect()
-    } else {
-        Vec::new()
-    };
+    }
 
-    let fields: Vec<(&Field, Vec<&TypeArgumentConfiguration>)> = parsed_fields
-        .iter()
-        .map(|f| {
-            let mut collector = TypeArgumentsCheckVisitor {
-                args: &generics,
-                matched: Vec::new(),
-            };
-            collector.visit_type(&f.ty);
+    // Whether the position of the field depends on which of the fields before it are enabled
+    fn field_shifts(&self, index: usize) -> bool {
+        self.shape == StructShape::Tuple && !self.field_cfg_predicates(index).is_empty()
+    }
 
-            (f, collector.matched)
-        })
-        .collect();
+    // The accessors of a field of a tuple struct, e.g. `name` and `name_mut`, or `__field_1` and
+    // `__field_1_mut` for an unnamed field which needs them
+    fn field_accessors(&self, index: usize) -> (Ident, Ident) {
+        match &self.fields[index].ident {
+            Some(ident) => {
+                let field_name = ident.to_string();
+                let field_name = field_name.trim_start_matches("r#");
+                (
+                    ident.clone(),
+                    Ident::new(&format!("{}_mut", field_name), ident.span()),
+                )
+            }
+            None => (
+                Ident::new(&format!("__field_{}", index), Span::call_site()),
+                Ident::new(&format!("__field_{}_mut", index), Span::call_site()),
+            ),
+        }
+    }
 
-    let token_streams = structs.iter().map(
-        |(
-            struct_name,
-            StructOutputConfiguration {
-                omitted_fields,
-                attributes,
-                included_fields,
-                upsert_fields,
-                upsert_fields_names,
-                is_tuple
-            },
-        )| {
-            let mut used_fields = LinkedHashSet::<&Field>::new();
-            let mut used_types = LinkedHashSet::<&Type>::new();
-            let mut used_generics = LinkedHashSet::<&GenericArgument>::new();
-            let mut used_wheres = LinkedHashSet::<&WherePredicate>::new();
-
-            let test_skip_predicate: Box<dyn Fn(&Field) -> bool> = if included_fields.is_empty() {
-                Box::new(|f: &Field| {
-                    let name = &f.ident.as_ref().unwrap().to_string();
-                    upsert_fields_names.contains(name) || omitted_fields.contains(name)
-                })
+    // A reference to the field of the receiver, e.g. `&self.name`, which goes through the accessors
+    // of the field when its position depends on the configuration
+    fn field_ref(
+        &self,
+        receiver: proc_macro2::TokenStream,
+        index: usize,
+        mutable: bool,
+    ) -> proc_macro2::TokenStream {
+        if self.field_shifts(index) {
+            let (getter, getter_mut) = self.field_accessors(index);
+            return if mutable {
+                quote! { #receiver.#getter_mut() }
             } else {
-                Box::new(|f: &Field| {
-                    let name = &f.ident.as_ref().unwrap().to_string();
-                    upsert_fields_names.contains(name) || !included_fields.contains(name)
-                })
+                quote! { #receiver.#getter() }
             };
+        }
 
-            for (f, type_args) in fields.iter() {
-                if test_skip_predicate(f) {
-                    continue;
-                }
-
-                if *is_tuple {
-                    used_types.insert(&f.ty);
-                } else {
-                    used_fields.insert(f);
-                }
+        let member = self.field_member(index);
+        if mutable {
+            quote! { &mut #receiver.#member }
+        } else {
+            quote! { &#receiver.#member }
+        }
+    }
 
-                for type_arg in type_args.iter() {
-                    used_generics.insert(type_arg.arg);
+    fn definition(&self) -> proc_macro2::TokenStream {
+        let OutputStruct {
+            name,
+            attributes,
+            visibility,
+            fields,
+            ..
+        } = self;
+        let generics = self.impl_generics();
+        let where_clause = self.where_clause();
 
-                    for w in wheres.iter() {
-                        for w_type_arg in w.1.iter() {
-                            if w_type_arg.arg == type_arg.arg {
-                                used_wheres.insert(w.0);
-                            }
-                        }
-                    }
+        match self.shape {
+            StructShape::Unit => quote! {
+                #(#attributes)*
+                #visibility struct #name;
+            },
+            StructShape::Tuple => {
+                let field_items = fields.iter().map(|f| {
+                    let attrs = &f.attrs;
+                    let vis = &f.vis;
+                    let ty = &f.ty;
+                    quote! { #(#attrs)* #vis #ty }
+                });
+                quote! {
+                    #(#attributes)*
+                    #visibility struct #name #generics (#(#field_items),*) #where_clause;
                 }
             }
-            if *is_tuple {
-                used_types.extend(upsert_fields.iter().map(|f| &f.ty));
-            } else {
-                used_fields.extend(upsert_fields.iter());
-            }
-
-            let field_items = Vec::from_iter(used_fields);
-            let type_items = Vec::from_iter(used_types);
-            let generic_items = Vec::from_iter(used_generics);
-            let where_items = Vec::from_iter(used_wheres);
-            let struct_name_ident = Ident::new(struct_name, Span::call_site());
-            if *is_tuple {
-                if where_items.is_empty() {
-                    quote! {
-                        #(#attributes)*
-                        #visibility struct #struct_name_ident <#(#generic_items),*> (#(#type_items),*);
-                    }
-                } else {
-                    quote! {
-                        #(#attributes)*
-                        #visibility struct #struct_name_ident <#(#generic_items),*> (#(#type_items),*) where #(#where_items),*;
+            StructShape::Named => quote! {
+                #(#attributes)*
+                #visibility struct #name #generics #where_clause {
+                    #(#fields),*
+                }
+            },
+            StructShape::Enum => {
+                let variants = &self.variants;
+                quote! {
+                    #(#attributes)*
+                    #visibility enum #name #generics #where_clause {
+                        #(#variants),*
                     }
                 }
-            } else if where_items.is_empty() {
+            }
+            StructShape::FieldNameEnum => {
+                let variants = fields.iter().enumerate().map(|(i, f)| {
+                    let attrs = &f.attrs;
+                    let variant = self.field_variant(i);
+                    quote! { #(#attrs)* #variant }
+                });
                 quote! {
                     #(#attributes)*
-                    #visibility struct #struct_name_ident <#(#generic_items),*> {
-                        #(#field_items),*
+                    #visibility enum #name {
+                        #(#variants),*
                     }
                 }
-            } else {
+            }
+            StructShape::FieldEnum => {
+                let variants = fields.iter().enumerate().map(|(i, f)| {
+                    let attrs = &f.attrs;
+                    let variant = self.field_variant(i);
+                    let ty = &f.ty;
+                    quote! { #(#attrs)* #variant(#ty) }
+                });
                 quote! {
                     #(#attributes)*
-                    #visibility struct #struct_name_ident <#(#generic_items),*> where #(#where_items),* {
-                        #(#field_items),*
+                    #visibility enum #name #generics #where_clause {
+                        #(#variants),*
                     }
                 }
             }
-        },
-    );
+        }
+    }
 
-    (quote! {
-       #(#token_streams)*
-    })
-    .into()
-}
+    // The name of the variant of a field enum for a given field, e.g. `first_name` becomes
+    // `FirstName` and the unnamed field `0` becomes `Field0`
+    fn field_variant_name(&self, index: usize) -> String {
+        match &self.fields[index].ident {
+            Some(ident) => ident
+                .to_string()
+                .trim_start_matches("r#")
+                .split('_')
+                .filter(|part| !part.is_empty())
+                .map(|part| {
+                    let mut chars = part.chars();
+                    chars
+                        .next()
+                        .unwrap()
+                        .to_uppercase()
+                        .chain(chars)
+                        .collect::<String>()
+                })
+                .collect(),
+            None => format!("Field{}", index),
+        }
+    }
 
-#[cfg(test)]
-mod tests {
-    use path_clean::PathClean;
-    use std::env;
-    use std::path::{Path, PathBuf};
-    use std::process::Command;
+    // Errors out if a field has no valid variant name, e.g. `__` or `_0`, or if two fields have the
+    // same one, e.g. `foo_bar` and `fooBar`
+    fn check_field_variants(&self) -> Result<()> {
+        let mut variants = Vec::<(String, usize)>::new();
+        for index in 0..self.fields.len() {
+            let variant = self.field_variant_name(index);
+            let field = self.field_name(index);
+            let span = self.fields[index]
+                .ident
+                .as_ref()
+                .map_or_else(Span::call_site, Ident::span);
+            if syn::parse_str::<Ident>(&variant).is_err() {
+                return Err(syn::Error::new(
+                    span,
+                    format!(
+                        "the field {} doesn't make a valid variant name for {}",
+                        field, self.name
+                    ),
+                ));
+            }
+            if let Some((_, other)) = variants.iter().find(|(v, _)| v == &variant) {
+                return Err(syn::Error::new(
+                    span,
+                    format!(
+                        "the fields {} and {} both make the variant {} of {}",
+                        self.field_name(*other),
+                        field,
+                        variant,
+                        self.name
+                    ),
+                ));
+            }
+            variants.push((variant, index));
+        }
+        Ok(())
+    }
 
-    pub fn absolute_path(path: impl AsRef<Path>) -> std::io::Result<PathBuf> {
-        let path = path.as_ref();
+    // The variant of a field enum for a given field, whose name is checked by
+    // `check_field_variants`
+    fn field_variant(&self, index: usize) -> Ident {
+        let span = self.fields[index]
+            .ident
+            .as_ref()
+            .map_or_else(Span::call_site, Ident::span);
+        Ident::new(&self.field_variant_name(index), span)
+    }
 
-        let absolute_path = if path.is_absolute() {
-            path.to_path_buf()
-        } else {
-            env::current_dir()?.join(path)
+    // The name of a field as it's written in the base, e.g. `first_name` or `0`
+    fn field_name(&self, index: usize) -> String {
+        match &self.fields[index].ident {
+            Some(ident) => ident.to_string().trim_start_matches("r#").to_string(),
+            None => index.to_string(),
         }
-        .clean();
-
-        Ok(absolute_path)
     }
 
-    fn run_for_fixture(fixture: &str) -> String {
-        let output = Command::new("cargo")
-            .arg("expand")
-            .arg(fixture)
-            .arg("--manifest-path")
-            .arg(format!(
-                "{}",
-                absolute_path("./test_fixtures/testbed/Cargo.toml")
-                    .unwrap()
-                    .display()
-            ))
-            .output()
-            .expect("Failed to spawn process");
+    // Conversions between the field name enum's variants and the names of the fields, as well as
+    // which of the given structs contain each field
+    fn field_name_enum_methods(
+        &self,
+        structs: Option<&[&OutputStruct]>,
+    ) -> proc_macro2::TokenStream {
+        let name = &self.name;
+        let visibility = &self.visibility;
+        let variants: Vec<Ident> = (0..self.fields.len())
+            .map(|i| self.field_variant(i))
+            .collect();
+        let names: Vec<String> = (0..self.fields.len()).map(|i| self.field_name(i)).collect();
+        let gates: Vec<proc_macro2::TokenStream> =
+            (0..self.fields.len()).map(|i| self.field_cfg(i)).collect();
+        let cfg = self.cfg();
+        let membership = structs.map(|structs| {
+            let members = self.fields.iter().map(|field| {
+                let struct_names = structs.iter().filter(|s| s.has_field(field)).map(|s| {
+                    let struct_cfg = s.cfg();
+                    let struct_name = s.name.to_string();
+                    quote! { #struct_cfg #struct_name }
+                });
+                quote! { &[#(#struct_names),*] }
+            });
+            quote! {
+                #visibility const fn variants(&self) -> &'static [&'static str] {
+                    match *self {
+                        #(#gates #name::#variants => #members,)*
+                    }
+                }
+            }
+        });
+
+        quote! {
+            #cfg
+            impl #name {
+                #visibility const ALL: &'static [#name] = &[#(#gates #name::#variants),*];
+
+                #visibility fn as_str(&self) -> &'static str {
+                    match *self {
+                        #(#gates #name::#variants => #names,)*
+                    }
+                }
+
+                #membership
+            }
+
+            #cfg
+            impl ::core::fmt::Display for #name {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    f.write_str(self.as_str())
+                }
+            }
 
-        String::from_utf8_lossy(&output.stdout)
-            .to_owned()
-            .to_string()
+            #cfg
+            impl ::core::str::FromStr for #name {
+                type Err = ::std::string::String;
+
+                fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                    match s {
+                        #(#gates #names => ::core::result::Result::Ok(#name::#variants),)*
+                        _ => ::core::result::Result::Err(["unknown field `", s, "`"].concat()),
+                    }
+                }
+            }
+        }
     }
 
-    #[test]
-    fn generics() {
-        insta::assert_snapshot!(run_for_fixture("generics"), @r###"
+    // Metadata about the fields, backed by `structout_runtime::FieldInfo`
+    fn field_info(&self) -> proc_macro2::TokenStream {
+        let name = &self.name;
+        let visibility = &self.visibility;
+        let impl_generics = self.impl_generics();
+        let type_generics = self.type_generics();
+        let where_clause = self.where_clause();
+        let names: Vec<String> = (0..self.fields.len()).map(|i| self.field_name(i)).collect();
+        let gates: Vec<proc_macro2::TokenStream> =
+            (0..self.fields.len()).map(|i| self.field_cfg(i)).collect();
+        let cfg = self.cfg();
+        // the count depends on the enabled fields if any of them is gated
+        let count = if gates.iter().all(|gate| gate.is_empty()) {
+            let count = proc_macro2::Literal::usize_unsuffixed(self.fields.len());
+            quote! { #count }
+        } else {
+            quote! { Self::FIELD_NAMES.len() }
+        };
+        let infos = self.fields.iter().enumerate().map(|(i, f)| {
+            let name = &names[i];
+            let gate = &gates[i];
+            let ty = &f.ty;
+            let attrs = &f.attrs;
+            quote! {
+                #gate
+                ::structout_runtime::FieldInfo {
+                    name: #name,
+                    ty: ::core::stringify!(#ty),
+                    attrs: &[#(::core::stringify!(#attrs)),*],
+                }
+            }
+        });
+
+        quote! {
+            #cfg
+            impl #impl_generics #name #type_generics #where_clause {
+                #visibility const FIELD_NAMES: &'static [&'static str] = &[#(#gates #names),*];
+                #visibility const FIELD_COUNT: usize = #count;
+                #visibility const FIELDS: &'static [::structout_runtime::FieldInfo] = &[#(#infos),*];
+            }
+        }
+    }
+
+    // Implementations of `structout_runtime::VisitFields` and `structout_runtime::VisitFieldsMut`,
+    // which are bound on the field types being `Debug` and `Any` respectively
+    fn visit_fields(&self) -> proc_macro2::TokenStream {
+        let name = &self.name;
+        let impl_generics = self.impl_generics();
+        let type_generics = self.type_generics();
+        let generic_types = self.generic_field_types();
+        let debug_where_clause = self.where_clause_with(
+            &generic_types
+                .iter()
+                .map(|ty| quote! { #ty: ::core::fmt::Debug })
+                .collect::<Vec<_>>(),
+        );
+        let any_where_clause = self.where_clause_with(
+            &generic_types
+                .iter()
+                .map(|ty| quote! { #ty: ::core::any::Any })
+                .collect::<Vec<_>>(),
+        );
+        let names: Vec<String> = (0..self.fields.len()).map(|i| self.field_name(i)).collect();
+        let fields: Vec<proc_macro2::TokenStream> = (0..self.fields.len())
+            .map(|i| self.field_ref(quote! { self }, i, false))
+            .collect();
+        let fields_mut: Vec<proc_macro2::TokenStream> = (0..self.fields.len())
+            .map(|i| self.field_ref(quote! { self }, i, true))
+            .collect();
+        let gates: Vec<proc_macro2::TokenStream> =
+            (0..self.fields.len()).map(|i| self.field_cfg(i)).collect();
+        let cfg = self.cfg();
+        // the visitor is unused when there are no fields, or when all of them are gated
+        let visitor = if gates.iter().all(|gate| !gate.is_empty()) {
+            quote! { _visitor }
+        } else {
+            quote! { visitor }
+        };
+
+        quote! {
+            #cfg
+            impl #impl_generics ::structout_runtime::VisitFields for #name #type_generics #debug_where_clause {
+                fn visit_fields(&self, #visitor: &mut dyn ::structout_runtime::Visitor) {
+                    #(#gates #visitor.field(#names, #fields);)*
+                }
+            }
+            #cfg
+            impl #impl_generics ::structout_runtime::VisitFieldsMut for #name #type_generics #any_where_clause {
+                fn visit_fields_mut(&mut self, #visitor: &mut dyn ::structout_runtime::VisitorMut) {
+                    #(#gates #visitor.field(#names, #fields_mut);)*
+                }
+            }
+        }
+    }
+
+    // Implementations of `structout_runtime::HasField` for each named field, given the module
+    // where the field markers are defined
+    fn has_field_impls(&self, module: &Ident) -> proc_macro2::TokenStream {
+        let name = &self.name;
+        let impl_generics = self.impl_generics();
+        let type_generics = self.type_generics();
+        let where_clause = self.where_clause();
+        let cfg = self.cfg();
+        let impls = self.fields.iter().enumerate().filter_map(|(i, f)| {
+            let ident = f.ident.as_ref()?;
+            let ty = &f.ty;
+            let field = self.field_ref(quote! { self }, i, false);
+            let field_mut = self.field_ref(quote! { self }, i, true);
+            let gate = self.field_cfg(i);
+            Some(quote! {
+                #cfg
+                #gate
+                impl #impl_generics ::structout_runtime::HasField<#module::#ident> for #name #type_generics #where_clause {
+                    type Type = #ty;
+
+                    fn get(&self) -> &Self::Type {
+                        #field
+                    }
+
+                    fn get_mut(&mut self) -> &mut Self::Type {
+                        #field_mut
+                    }
+                }
+            })
+        });
+
+        quote! { #(#impls)* }
+    }
+
+    // The implementation of a common trait, given the fields which are common to all implementors
+    fn common_trait_impl(
+        &self,
+        common_trait: &proc_macro2::TokenStream,
+        fields: &[&Field],
+        setters: bool,
+    ) -> proc_macro2::TokenStream {
+        let name = &self.name;
+        let impl_generics = self.impl_generics();
+        let type_generics = self.type_generics();
+        let where_clause = self.where_clause();
+        let methods = fields.iter().map(|field| {
+            let index = self
+                .fields
+                .iter()
+                .position(|f| f.ident == field.ident)
+                .unwrap();
+            let gate = self.field_cfg(index);
+            let getter = field.ident.as_ref().unwrap();
+            let ty = &field.ty;
+            let field_ref = self.field_ref(quote! { self }, index, false);
+            let setter = if setters {
+                let setter = setter_ident(getter);
+                let assignment = if self.field_shifts(index) {
+                    let field_mut = self.field_ref(quote! { self }, index, true);
+                    quote! { *#field_mut = value; }
+                } else {
+                    let member = self.field_member(index);
+                    quote! { self.#member = value; }
+                };
+                quote! {
+                    #gate
+                    fn #setter(&mut self, value: #ty) {
+                        #assignment
+                    }
+                }
+            } else {
+                quote! {}
+            };
+            quote! {
+                #gate
+                fn #getter(&self) -> &#ty {
+                    #field_ref
+                }
+                #setter
+            }
+        });
+        let cfg = self.cfg();
+
+        quote! {
+            #cfg
+            impl #impl_generics #common_trait for #name #type_generics #where_clause {
+                #(#methods)*
+            }
+        }
+    }
+
+    // Conversions into the enum wrapping the given structs, along with accessors for the fields
+    // which have the same type in all of the structs which contain them
+    fn any_methods(&self, members: &[&OutputStruct]) -> proc_macro2::TokenStream {
+        let name = &self.name;
+        let visibility = &self.visibility;
+        let impl_generics = self.impl_generics();
+        let type_generics = self.type_generics();
+        let where_clause = self.where_clause();
+
+        let cfg = self.cfg();
+        let conversions = members.iter().map(|member| {
+            let member_name = &member.name;
+            let member_type_generics = member.type_generics();
+            let member_cfg = member.cfg();
+            quote! {
+                #cfg
+                #member_cfg
+                impl #impl_generics ::core::convert::From<#member_name #member_type_generics> for #name #type_generics #where_clause {
+                    fn from(value: #member_name #member_type_generics) -> Self {
+                        #name::#member_name(value)
+                    }
+                }
+            }
+        });
+
+        let mut fields = Vec::<&Field>::new();
+        for member in members.iter() {
+            for field in member.fields.iter() {
+                if field.ident.is_some() && !fields.iter().any(|f| f.ident == field.ident) {
+                    fields.push(field);
+                }
+            }
+        }
+        let accessors = fields.into_iter().filter_map(|field| {
+            let with_field: Vec<&&OutputStruct> = members
+                .iter()
+                .filter(|m| m.fields.iter().any(|f| f.ident == field.ident))
+                .collect();
+            if with_field.iter().any(|m| !m.has_field(field)) {
+                return None;
+            }
+
+            let getter = field.ident.as_ref().unwrap();
+            let ty = &field.ty;
+            // the field is gated the same way in all of the structs which have it
+            let gate = cfg_attributes(&field.attrs);
+            let arms = with_field.iter().map(|m| {
+                let member_name = &m.name;
+                let index = m.fields.iter().position(|f| f.ident == field.ident).unwrap();
+                let field = m.field_ref(quote! { value }, index, false);
+                (m.cfg(), member_name, field)
+            });
+            // the structs may all be gated out, leaving the enum without variants
+            Some(if with_field.len() == members.len() {
+                let arms = arms.map(|(member_cfg, member_name, field)| {
+                    quote! { #member_cfg #name::#member_name(ref value) => #field, }
+                });
+                quote! {
+                    #gate
+                    #visibility fn #getter(&self) -> &#ty {
+                        match *self {
+                            #(#arms)*
+                        }
+                    }
+                }
+            } else {
+                let arms = arms.map(|(member_cfg, member_name, field)| {
+                    quote! { #member_cfg #name::#member_name(ref value) => ::core::option::Option::Some(#field), }
+                });
+                // the structs without the field may all be gated out
+                let allow_unreachable = if members
+                    .iter()
+                    .any(|m| !m.has_field(field) && !m.cfg().is_empty())
+                {
+                    quote! { #[allow(unreachable_patterns)] }
+                } else {
+                    quote! {}
+                };
+                quote! {
+                    #gate
+                    #visibility fn #getter(&self) -> ::core::option::Option<&#ty> {
+                        match *self {
+                            #(#arms)*
+                            #allow_unreachable
+                            _ => ::core::option::Option::None,
+                        }
+                    }
+                }
+            })
+        });
+
+        quote! {
+            #(#conversions)*
+            #cfg
+            impl #impl_generics #name #type_generics #where_clause {
+                #(#accessors)*
+            }
+        }
+    }
+
+    // The items of the template which only use fields this struct has
+    fn impl_template(&self, template: &ImplTemplate) -> proc_macro2::TokenStream {
+        let name = &self.name;
+        let impl_generics = self.impl_generics();
+        let type_generics = self.type_generics();
+        let where_clause = self.where_clause();
+        let items: Vec<&ImplItem> = template
+            .items
+            .iter()
+            .filter(|item| self.missing_fields(item).is_empty())
+            .collect();
+        if items.is_empty() {
+            return quote! {};
+        }
+        // the items are gated along with the fields they use
+        let items = items.into_iter().map(|item| {
+            let mut visitor = SelfFieldsVisitor::default();
+            visitor.visit_impl_item(item);
+            let gates = (0..self.fields.len())
+                .filter(|i| {
+                    let member = member_name(&self.field_member(*i));
+                    visitor.fields.iter().any(|f| member_name(f) == member)
+                })
+                .map(|i| self.field_cfg(i));
+            quote! { #(#gates)* #item }
+        });
+        let cfg = self.cfg();
+
+        quote! {
+            #cfg
+            impl #impl_generics #name #type_generics #where_clause {
+                #(#items)*
+            }
+        }
+    }
+
+    // The fields accessed by an item of an impl template which this struct doesn't have
+    fn missing_fields(&self, item: &ImplItem) -> Vec<Member> {
+        let mut visitor = SelfFieldsVisitor::default();
+        visitor.visit_impl_item(item);
+        let members: Vec<String> = (0..self.fields.len())
+            .map(|i| member_name(&self.field_member(i)))
+            .collect();
+        visitor
+            .fields
+            .into_iter()
+            .filter(|f| !members.contains(&member_name(f)))
+            .collect()
+    }
+
+    // The generics of `other` which aren't used in this output
+    fn missing_generics<'a>(&self, other: &'a OutputStruct) -> Vec<&'a GenericArgument> {
+        other
+            .generics
+            .iter()
+            .filter(|arg| !self.generics.contains(arg))
+            .copied()
+            .collect()
+    }
+
+    // Methods for applying the field enum's updates to the base, given that all of the enum's
+    // fields are present in the base
+    fn field_enum_methods(&self, base: Option<&OutputStruct>) -> proc_macro2::TokenStream {
+        let base = match base {
+            Some(base)
+                if !self.fields.is_empty() && self.fields.iter().all(|f| base.has_field(f)) =>
+            {
+                base
+            }
+            _ => return quote! {},
+        };
+
+        let name = &self.name;
+        let visibility = &self.visibility;
+        let impl_generics = self.impl_generics();
+        let type_generics = self.type_generics();
+        let where_clause = self.where_clause();
+        let base_name = &base.name;
+        let base_generics = base.type_generics();
+        let base_where_clause = base.where_clause();
+        let missing_generics = self.missing_generics(base);
+        let method_generics = if missing_generics.is_empty() {
+            quote! {}
+        } else {
+            quote! { <#(#missing_generics),*> }
+        };
+        let variants: Vec<Ident> = (0..self.fields.len())
+            .map(|i| self.field_variant(i))
+            .collect();
+        let idents: Vec<&Ident> = (0..self.fields.len())
+            .map(|i| self.field_ident(i))
+            .collect();
+        let gates: Vec<proc_macro2::TokenStream> =
+            (0..self.fields.len()).map(|i| self.field_cfg(i)).collect();
+        let cfg = self.cfg();
+        let base_cfg = base.cfg();
+
+        quote! {
+            #cfg
+            #base_cfg
+            impl #impl_generics #name #type_generics #where_clause {
+                #visibility fn apply #method_generics (self, target: &mut #base_name #base_generics) #base_where_clause {
+                    match self {
+                        #(#gates #name::#variants(value) => target.#idents = value,)*
+                    }
+                }
+
+                #visibility fn into_field_updates #method_generics (base: #base_name #base_generics) -> ::std::vec::IntoIter<Self> #base_where_clause {
+                    ::std::vec::Vec::from([#(#gates #name::#variants(base.#idents)),*]).into_iter()
+                }
+            }
+        }
+    }
+
+    fn is_subset_of(&self, full: &OutputStruct) -> bool {
+        let signature = |v: &Variant| quote!(#v).to_string();
+        let full_variants: LinkedHashSet<_> = full.variants.iter().map(signature).collect();
+        self.variants
+            .iter()
+            .all(|v| full_variants.contains(&signature(v)))
+    }
+
+    // From<Subset> for Full and TryFrom<Full> for Subset, given that the subset only contains
+    // variants of the full enum
+    fn enum_conversions(&self, full: &OutputStruct) -> proc_macro2::TokenStream {
+        let subset_name = &self.name;
+        let full_name = &full.name;
+        let impl_generics = full.impl_generics();
+        let subset_generics = self.type_generics();
+        let full_generics = full.type_generics();
+        let where_clause = full.where_clause();
+
+        let gates: Vec<proc_macro2::TokenStream> = self
+            .variants
+            .iter()
+            .map(|v| cfg_attributes(&v.attrs))
+            .collect();
+        let cfg = self.cfg();
+        let full_cfg = full.cfg();
+        let (subset_patterns, full_patterns): (Vec<_>, Vec<_>) = self
+            .variants
+            .iter()
+            .map(|v| {
+                let ident = &v.ident;
+                let bindings = v.fields.iter().enumerate().map(|(i, f)| match &f.ident {
+                    Some(ident) => quote! { #ident },
+                    None => {
+                        let binding = Ident::new(&format!("__field{}", i), Span::call_site());
+                        quote! { #binding }
+                    }
+                });
+                let bindings = match &v.fields {
+                    syn::Fields::Named(_) => quote! { { #(#bindings),* } },
+                    syn::Fields::Unnamed(_) => quote! { ( #(#bindings),* ) },
+                    syn::Fields::Unit => quote! {},
+                };
+                (
+                    quote! { #subset_name::#ident #bindings },
+                    quote! { #full_name::#ident #bindings },
+                )
+            })
+            .unzip();
+        let fallback = if self.variants.len() < full.variants.len() {
+            quote! { value => ::core::result::Result::Err(value), }
+        } else {
+            quote! {}
+        };
+
+        quote! {
+            #cfg
+            #full_cfg
+            impl #impl_generics ::core::convert::From<#subset_name #subset_generics> for #full_name #full_generics #where_clause {
+                fn from(value: #subset_name #subset_generics) -> Self {
+                    match value {
+                        #(#gates #subset_patterns => #full_patterns,)*
+                    }
+                }
+            }
+            #cfg
+            #full_cfg
+            impl #impl_generics ::core::convert::TryFrom<#full_name #full_generics> for #subset_name #subset_generics #where_clause {
+                type Error = #full_name #full_generics;
+
+                fn try_from(value: #full_name #full_generics) -> ::core::result::Result<Self, Self::Error> {
+                    match value {
+                        #(#gates #full_patterns => ::core::result::Result::Ok(#subset_patterns),)*
+                        #fallback
+                    }
+                }
+            }
+        }
+    }
+
+    // fields are the same if they have the same name and type, and are gated the same way
+    fn has_field(&self, field: &Field) -> bool {
+        let signature = |f: &Field| {
+            let ty = &f.ty;
+            (
+                quote!(#ty).to_string(),
+                cfg_attributes(&f.attrs).to_string(),
+            )
+        };
+        let field_signature = signature(field);
+        self.fields
+            .iter()
+            .any(|f| f.ident == field.ident && signature(f) == field_signature)
+    }
+
+    fn has_same_fields(&self, other: &OutputStruct) -> bool {
+        let signature = |f: &Field| {
+            let ty = &f.ty;
+            (
+                f.ident.clone(),
+                quote!(#ty).to_string(),
+                cfg_attributes(&f.attrs).to_string(),
+            )
+        };
+        let fields: LinkedHashSet<_> = self.fields.iter().map(signature).collect();
+        let other_fields: LinkedHashSet<_> = other.fields.iter().map(signature).collect();
+        fields.len() == self.fields.len() && fields == other_fields
+    }
+
+    // Positional constants and accessors named after the original fields of a tuple struct
+    fn tuple_accessors(&self) -> proc_macro2::TokenStream {
+        let name = &self.name;
+        let impl_generics = self.impl_generics();
+        let type_generics = self.type_generics();
+        let where_clause = self.where_clause();
+
+        let cfg = self.cfg();
+
+        // the positions of the fields depend on which of them are enabled
+        let impls = self
+            .field_configurations()
+            .into_iter()
+            .map(|(configuration, fields)| {
+                let items = fields.iter().enumerate().filter_map(|(i, &field)| {
+                    let f = &self.fields[field];
+                    let vis = &f.vis;
+                    let ty = &f.ty;
+                    let index = syn::Index::from(i);
+                    let (getter, getter_mut) = self.field_accessors(field);
+                    let ident = match f.ident.as_ref() {
+                        Some(ident) => ident,
+                        // the generated impls access shifting unnamed fields through these
+                        None if self.field_shifts(field) => {
+                            return Some(quote! {
+                                #[allow(dead_code)]
+                                fn #getter(&self) -> &#ty {
+                                    &self.#index
+                                }
+                                #[allow(dead_code)]
+                                fn #getter_mut(&mut self) -> &mut #ty {
+                                    &mut self.#index
+                                }
+                            });
+                        }
+                        None => return None,
+                    };
+                    let field_name = ident.to_string();
+                    let field_name = field_name.trim_start_matches("r#");
+                    let index_const = Ident::new(
+                        &format!("{}_INDEX", field_name.to_uppercase()),
+                        ident.span(),
+                    );
+                    Some(quote! {
+                        #vis const #index_const: usize = #index;
+                        #vis fn #getter(&self) -> &#ty {
+                            &self.#index
+                        }
+                        #vis fn #getter_mut(&mut self) -> &mut #ty {
+                            &mut self.#index
+                        }
+                    })
+                });
+
+                quote! {
+                    #cfg
+                    #configuration
+                    impl #impl_generics #name #type_generics #where_clause {
+                        #(#items)*
+                    }
+                }
+            });
+
+        quote! { #(#impls)* }
+    }
+
+    // From implementations between a tuple struct and a named struct with the same fields
+    fn conversions(&self, named: &OutputStruct) -> proc_macro2::TokenStream {
+        let tuple_name = &self.name;
+        let named_name = &named.name;
+        let impl_generics = self.impl_generics();
+        let tuple_generics = self.type_generics();
+        let named_generics = named.type_generics();
+        let where_clause = self.where_clause();
+
+        let cfg = self.cfg();
+        let named_cfg = named.cfg();
+        let tuple_idents = (0..self.fields.len()).map(|i| self.field_ident(i));
+        let gates = (0..self.fields.len()).map(|i| self.field_cfg(i));
+
+        // the positions of the fields depend on which of them are enabled, unlike their names
+        let configurations = self.field_configurations();
+        let from_tuple = configurations.into_iter().map(|(configuration, fields)| {
+            let indexes = (0..fields.len()).map(syn::Index::from);
+            let named_idents = fields.iter().map(|i| self.field_ident(*i));
+            quote! {
+                #cfg
+                #named_cfg
+                #configuration
+                impl #impl_generics ::core::convert::From<#tuple_name #tuple_generics> for #named_name #named_generics #where_clause {
+                    fn from(value: #tuple_name #tuple_generics) -> Self {
+                        #named_name {
+                            #(#named_idents: value.#indexes),*
+                        }
+                    }
+                }
+            }
+        });
+
+        quote! {
+            #(#from_tuple)*
+            #cfg
+            #named_cfg
+            impl #impl_generics ::core::convert::From<#named_name #named_generics> for #tuple_name #tuple_generics #where_clause {
+                fn from(value: #named_name #named_generics) -> Self {
+                    #tuple_name(#(#gates value.#tuple_idents),*)
+                }
+            }
+        }
+    }
+}
+
+struct TypeArgumentConfiguration<'ast> {
+    arg: &'ast GenericArgument,
+    identifiers: LinkedHashSet<String>,
+}
+
+struct ItemConfiguration {
+    // the identifier for named fields and variants or the index for unnamed fields
+    name: String,
+    // the item as it should be output, i.e. without the #[structout(...)] attributes
+    item: Item,
+    tags: LinkedHashSet<String>,
+}
+
+// Options which apply to the whole definition, given through `#[structout(...)]` on the base
+#[derive(Default)]
+struct DefinitionOptions {
+    field_info: Option<Path>,
+    visit_fields: Option<Path>,
+    // the path of the option and the name of the module holding the field markers
+    has_field: Option<(Path, Ident)>,
+    // the path of the option and the traits which all structs should implement
+    common_traits: Vec<(Path, CommonTrait)>,
+    no_auto_doc: Option<Path>,
+    // the path of the option and the module documented with the matrix, if not the base
+    field_matrix: Option<(Path, Option<Ident>)>,
+    // the path of the option and the name of the enum wrapping all structs
+    any: Option<(Path, Ident)>,
+}
+
+fn parse_definition_options(attrs: &[Attribute]) -> Result<(Vec<Attribute>, DefinitionOptions)> {
+    let mut options = DefinitionOptions::default();
+    let mut output = Vec::new();
+
+    for attr in attrs.iter() {
+        if !attr.path.is_ident("structout") {
+            output.push(attr.clone());
+            continue;
+        }
+
+        let nested = match attr.parse_meta()? {
+            Meta::List(list) => list.nested,
+            meta => return Err(syn::Error::new_spanned(meta, "expected #[structout(...)]")),
+        };
+        for option in nested.iter() {
+            match option {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("field_info") => {
+                    options.field_info = Some(path.clone());
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("visit_fields") => {
+                    options.visit_fields = Some(path.clone());
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("no_auto_doc") => {
+                    options.no_auto_doc = Some(path.clone());
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("field_matrix") => {
+                    options.field_matrix = Some((path.clone(), None));
+                }
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("field_matrix") => {
+                    let nested = &list.nested;
+                    options.field_matrix =
+                        Some((list.path.clone(), Some(syn::parse2(quote! { #nested })?)));
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("has_field") => {
+                    options.has_field =
+                        Some((path.clone(), Ident::new("fields", Span::call_site())));
+                }
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("has_field") => {
+                    let module = match list.nested.first() {
+                        Some(NestedMeta::Meta(Meta::Path(module)))
+                            if list.nested.len() == 1 && module.get_ident().is_some() =>
+                        {
+                            module.get_ident().unwrap().clone()
+                        }
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                list,
+                                "expected has_field(module_name)",
+                            ))
+                        }
+                    };
+                    options.has_field = Some((list.path.clone(), module));
+                }
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("common_trait") => {
+                    let nested = &list.nested;
+                    options
+                        .common_traits
+                        .push((list.path.clone(), syn::parse2(quote! { #nested })?));
+                }
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("any") => {
+                    let nested = &list.nested;
+                    options.any = Some((list.path.clone(), syn::parse2(quote! { #nested })?));
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        option,
+                        "is not a valid structout definition option",
+                    ))
+                }
+            }
+        }
+    }
+
+    Ok((output, options))
+}
+
+// Removes what's given by `strip` from an attribute, where:
+// - a path removes the whole attribute, e.g. `serde`
+// - a list removes the matching items from the attribute's list, e.g. `derive(Default)`, and the
+// attribute itself once its list is empty
+// - a name-value removes the attribute if it's equal, e.g. `doc = "..."`
+fn strip_attribute(attr: Attribute, strip: &Meta) -> Option<Attribute> {
+    if let Meta::Path(path) = strip {
+        return if path == &attr.path { None } else { Some(attr) };
+    }
+    let meta = match attr.parse_meta() {
+        Ok(meta) => meta,
+        Err(_) => return Some(attr),
+    };
+
+    match (strip, &meta) {
+        (Meta::List(strip_list), Meta::List(list)) if strip_list.path == list.path => {
+            let removed: Vec<String> = strip_list
+                .nested
+                .iter()
+                .map(|nested| quote!(#nested).to_string())
+                .collect();
+            let kept: Vec<&NestedMeta> = list
+                .nested
+                .iter()
+                .filter(|nested| !removed.contains(&quote!(#nested).to_string()))
+                .collect();
+            if kept.is_empty() {
+                None
+            } else {
+                let path = &list.path;
+                Some(parse_quote! { #[#path(#(#kept),*)] })
+            }
+        }
+        (Meta::NameValue(_), _) if strip == &meta => None,
+        _ => Some(attr),
+    }
+}
+
+// Merges the derives of the attributes and the given ones into a single deduplicated derive, which
+// takes the place of the first derive (or goes first if there's none), minus the underived traits
+fn merge_derives(
+    attributes: Vec<Attribute>,
+    derives: &[&Path],
+    underives: &[&Path],
+) -> Vec<Attribute> {
+    let mut output = Vec::new();
+    let mut position = None;
+    let mut merged: Vec<Path> = Vec::new();
+    let mut merge = |path: &Path| {
+        let name = quote!(#path).to_string();
+        if !merged.iter().any(|p| quote!(#p).to_string() == name) {
+            merged.push(path.clone());
+        }
+    };
+
+    for attr in attributes {
+        if attr.path.is_ident("derive") {
+            if let Ok(paths) = attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)
+            {
+                position.get_or_insert(output.len());
+                paths.iter().for_each(&mut merge);
+                continue;
+            }
+        }
+        output.push(attr);
+    }
+    derives.iter().for_each(|path| merge(path));
+
+    let underived: Vec<String> = underives.iter().map(|p| quote!(#p).to_string()).collect();
+    merged.retain(|p| !underived.contains(&quote!(#p).to_string()));
+    if !merged.is_empty() {
+        output.insert(
+            position.unwrap_or(0),
+            parse_quote! { #[derive(#(#merged),*)] },
+        );
+    }
+
+    output
+}
+
+// The accessors and conversions of a tuple struct depend on the positions of its fields, so they're
+// generated for each combination of the predicates its fields are gated behind
+const MAX_TUPLE_FIELD_PREDICATES: usize = 8;
+
+// The predicates of the `#[cfg(...)]` attributes, e.g. `feature = "server"`
+fn cfgs(attrs: &[Attribute]) -> Vec<proc_macro2::TokenStream> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("cfg"))
+        .filter_map(|attr| attr.parse_args().ok())
+        .collect()
+}
+
+// The conditional compilation part of an attribute, i.e. `cfg(...)` itself or the nested `cfg(...)`
+// of a `cfg_attr(...)`, e.g. `cfg_attr(test, cfg(unix), allow(dead_code))` becomes
+// `cfg_attr(test, cfg(unix))`
+fn cfg_meta(meta: &Meta) -> Option<Meta> {
+    match meta {
+        Meta::List(list) if list.path.is_ident("cfg") => Some(meta.clone()),
+        Meta::List(list) if list.path.is_ident("cfg_attr") => {
+            let mut nested = list.nested.iter();
+            let predicate = nested.next()?;
+            let kept: Vec<Meta> = nested
+                .filter_map(|nested| match nested {
+                    NestedMeta::Meta(meta) => cfg_meta(meta),
+                    NestedMeta::Lit(_) => None,
+                })
+                .collect();
+            if kept.is_empty() {
+                None
+            } else {
+                Some(parse_quote! { cfg_attr(#predicate, #(#kept),*) })
+            }
+        }
+        _ => None,
+    }
+}
+
+// The `#[cfg(...)]` attributes among the given ones, which the impls of what they gate have to
+// carry as well
+fn cfg_attributes(attrs: &[Attribute]) -> proc_macro2::TokenStream {
+    let attrs = attrs.iter().filter(|attr| attr.path.is_ident("cfg"));
+    quote! { #(#attrs)* }
+}
+
+// The `#[cfg(...)]` attributes which all of the structs are gated behind
+fn shared_cfg_attributes(structs: &[&OutputStruct]) -> Vec<Attribute> {
+    let signature = |attr: &Attribute| quote!(#attr).to_string();
+    match structs.first() {
+        Some(first) => first
+            .attributes
+            .iter()
+            .filter(|attr| attr.path.is_ident("cfg"))
+            .filter(|attr| {
+                structs.iter().all(|s| {
+                    s.attributes
+                        .iter()
+                        .any(|other| signature(other) == signature(attr))
+                })
+            })
+            .cloned()
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+// The doc lines of a markdown table showing which fields each struct has, where the links to the
+// structs are relative to `scope` if given, e.g. `super::` for the docs of a module
+fn field_matrix(outputs: &[OutputStruct], scope: Option<&str>) -> Vec<String> {
+    let structs: Vec<&OutputStruct> = outputs
+        .iter()
+        .filter(|o| {
+            matches!(
+                o.shape,
+                StructShape::Named | StructShape::Tuple | StructShape::Unit
+            )
+        })
+        .collect();
+    let field_names: Vec<Vec<String>> = structs
+        .iter()
+        .map(|o| (0..o.fields.len()).map(|i| o.field_name(i)).collect())
+        .collect();
+    let mut rows = Vec::<&String>::new();
+    for name in field_names.iter().flatten() {
+        if !rows.contains(&name) {
+            rows.push(name);
+        }
+    }
+
+    let mut lines = vec![
+        " Fields of the structs generated from this definition:".to_string(),
+        String::new(),
+        format!(
+            " | Field | {} |",
+            structs
+                .iter()
+                .map(|o| match scope {
+                    Some(scope) => format!("[`{name}`]({}{name})", scope, name = o.name),
+                    None => format!("[`{}`]", o.name),
+                })
+                .collect::<Vec<_>>()
+                .join(" | ")
+        ),
+        format!(" | --- |{}", " :---: |".repeat(structs.len())),
+    ];
+    for row in rows {
+        let cells: Vec<&str> = field_names
+            .iter()
+            .map(|names| if names.contains(row) { "✓" } else { " " })
+            .collect();
+        lines.push(format!(" | `{}` | {} |", row, cells.join(" | ")));
+    }
+    lines
+}
+
+// Splits a base item's attributes into the ones which should be output and the tags declared
+// through #[structout(tag = "...")]
+fn parse_item_tags(item: &Item) -> Result<(Item, LinkedHashSet<String>)> {
+    let mut tags = LinkedHashSet::<String>::new();
+    let mut output = item.clone();
+    output.attrs_mut().clear();
+
+    for attr in item.attrs().iter() {
+        if !attr.path.is_ident("structout") {
+            output.attrs_mut().push(attr.clone());
+            continue;
+        }
+
+        let options = match attr.parse_meta()? {
+            Meta::List(list) => list.nested,
+            meta => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    "expected #[structout(tag = \"...\")]",
+                ))
+            }
+        };
+        for option in options.iter() {
+            match option {
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("tag") => match &nv.lit {
+                    Lit::Str(tag) => {
+                        tags.insert(tag.value());
+                    }
+                    lit => return Err(syn::Error::new_spanned(lit, "tags should be strings")),
+                },
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        option,
+                        "is not a valid structout item option",
+                    ))
+                }
+            }
+        }
+    }
+
+    Ok((output, tags))
+}
+
+// Checks if a type matches a pattern given in omit_type or include_type, where:
+// - `_` matches any type (or lifetime)
+// - a path matches on its trailing segments, e.g. `Secret<_>` matches `secrecy::Secret<String>`
+// - a path without arguments matches the same path with any arguments, e.g. `Option` matches
+// `Option<u32>`
+fn type_matches(pattern: &Type, ty: &Type) -> bool {
+    match (pattern, ty) {
+        (Type::Infer(_), _) => true,
+        (Type::Group(pattern), _) => type_matches(&pattern.elem, ty),
+        (_, Type::Group(ty)) => type_matches(pattern, &ty.elem),
+        (Type::Paren(pattern), _) => type_matches(&pattern.elem, ty),
+        (_, Type::Paren(ty)) => type_matches(pattern, &ty.elem),
+        (Type::Path(pattern), Type::Path(ty)) => {
+            pattern.qself.is_none() && ty.qself.is_none() && path_matches(&pattern.path, &ty.path)
+        }
+        (Type::Reference(pattern), Type::Reference(ty)) => {
+            pattern.mutability.is_some() == ty.mutability.is_some()
+                && type_matches(&pattern.elem, &ty.elem)
+        }
+        (Type::Slice(pattern), Type::Slice(ty)) => type_matches(&pattern.elem, &ty.elem),
+        (Type::Array(pattern), Type::Array(ty)) => {
+            let (pattern_len, ty_len) = (&pattern.len, &ty.len);
+            type_matches(&pattern.elem, &ty.elem)
+                && quote!(#pattern_len).to_string() == quote!(#ty_len).to_string()
+        }
+        (Type::Tuple(pattern), Type::Tuple(ty)) => {
+            pattern.elems.len() == ty.elems.len()
+                && pattern
+                    .elems
+                    .iter()
+                    .zip(ty.elems.iter())
+                    .all(|(pattern, ty)| type_matches(pattern, ty))
+        }
+        _ => quote!(#pattern).to_string() == quote!(#ty).to_string(),
+    }
+}
+
+fn path_matches(pattern: &Path, path: &Path) -> bool {
+    if pattern.segments.len() > path.segments.len()
+        || (pattern.leading_colon.is_some() && pattern.segments.len() != path.segments.len())
+    {
+        return false;
+    }
+
+    pattern
+        .segments
+        .iter()
+        .rev()
+        .zip(path.segments.iter().rev())
+        .all(|(pattern, segment)| {
+            pattern.ident == segment.ident
+                && match (&pattern.arguments, &segment.arguments) {
+                    (PathArguments::None, _) => true,
+                    (
+                        PathArguments::AngleBracketed(pattern),
+                        PathArguments::AngleBracketed(args),
+                    ) => {
+                        pattern.args.len() == args.args.len()
+                            && pattern.args.iter().zip(args.args.iter()).all(
+                                |(pattern, arg)| match (pattern, arg) {
+                                    (GenericArgument::Type(pattern), GenericArgument::Type(ty)) => {
+                                        type_matches(pattern, ty)
+                                    }
+                                    (
+                                        GenericArgument::Lifetime(pattern),
+                                        GenericArgument::Lifetime(lifetime),
+                                    ) => pattern.ident == "_" || pattern == lifetime,
+                                    _ => quote!(#pattern).to_string() == quote!(#arg).to_string(),
+                                },
+                            )
+                    }
+                    (PathArguments::Parenthesized(pattern), PathArguments::Parenthesized(args)) => {
+                        quote!(#pattern).to_string() == quote!(#args).to_string()
+                    }
+                    _ => false,
+                }
+        })
+}
+
+fn schema_file_path(path: &LitStr) -> std::path::PathBuf {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    std::path::Path::new(&manifest_dir).join(path.value())
+}
+
+// The fields of the object described by a JSON Schema file
+fn schema_file_fields(path: &LitStr) -> Result<Vec<Field>> {
+    let file = schema_file_path(path);
+    let contents = std::fs::read_to_string(&file).map_err(|err| {
+        syn::Error::new_spanned(
+            path,
+            format!("couldn't read {}: {}", file.to_string_lossy(), err),
+        )
+    })?;
+    let schema: serde_json::Value = serde_json::from_str(&contents).map_err(|err| {
+        syn::Error::new_spanned(
+            path,
+            format!("{} isn't valid JSON: {}", file.to_string_lossy(), err),
+        )
+    })?;
+    schema_fields(&schema).map_err(|(json_path, message)| {
+        syn::Error::new_spanned(
+            path,
+            format!("{}: {} at {}", path.value(), message, json_path),
+        )
+    })
+}
+
+// Properties become fields, in the order they're defined, and the ones which aren't required are
+// optional. Errors come with the JSON path of the offending value, e.g. `$.properties.age.type`
+fn schema_fields(schema: &serde_json::Value) -> std::result::Result<Vec<Field>, (String, String)> {
+    if let Some(ty) = schema.get("type") {
+        if ty != "object" {
+            return Err((
+                "$.type".to_string(),
+                "the schema has to describe an object".to_string(),
+            ));
+        }
+    }
+    let properties = match schema.get("properties") {
+        Some(serde_json::Value::Object(properties)) => properties,
+        _ => {
+            return Err((
+                "$.properties".to_string(),
+                "expected an object of properties".to_string(),
+            ))
+        }
+    };
+    let required: Vec<&str> = match schema.get("required") {
+        None => Vec::new(),
+        Some(serde_json::Value::Array(names)) => names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                name.as_str().ok_or_else(|| {
+                    (
+                        format!("$.required[{}]", i),
+                        "expected a property name".to_string(),
+                    )
+                })
+            })
+            .collect::<std::result::Result<_, _>>()?,
+        Some(_) => {
+            return Err((
+                "$.required".to_string(),
+                "expected an array of property names".to_string(),
+            ))
+        }
+    };
+
+    properties
+        .iter()
+        .map(|(name, property)| {
+            let json_path = format!("$.properties.{}", name);
+            let ident = match syn::parse_str::<Ident>(name)
+                .or_else(|_| syn::parse_str::<Ident>(&["r#", name].concat()))
+            {
+                Ok(ident) => ident,
+                Err(_) => return Err((json_path, format!("`{}` is not a valid field name", name))),
+            };
+            let (ty, nullable) = schema_type(property, &json_path)?;
+            let ty: Type = if nullable || !required.contains(&name.as_str()) {
+                parse_quote! { Option<#ty> }
+            } else {
+                ty
+            };
+            let attrs = match property.get("description").and_then(|d| d.as_str()) {
+                Some(description) => {
+                    let description = [" ", description].concat();
+                    vec![parse_quote! { #[doc = #description] }]
+                }
+                None => Vec::new(),
+            };
+
+            Ok(Field {
+                attrs,
+                vis: Visibility::Inherited,
+                ident: Some(ident),
+                colon_token: Some(Default::default()),
+                ty,
+            })
+        })
+        .collect()
+}
+
+// The Rust type of a schema, and whether it allows `null`
+fn schema_type(
+    schema: &serde_json::Value,
+    json_path: &str,
+) -> std::result::Result<(Type, bool), (String, String)> {
+    let error =
+        |suffix: &str, message: &str| Err(([json_path, suffix].concat(), message.to_string()));
+
+    // references are mapped to the type named after their last segment, e.g.
+    // `#/definitions/Address` to `Address`
+    if let Some(reference) = schema.get("$ref") {
+        let name = reference
+            .as_str()
+            .and_then(|r| r.rsplit('/').next())
+            .and_then(|name| syn::parse_str::<Ident>(name).ok());
+        return match name {
+            Some(name) => Ok((parse_quote! { #name }, false)),
+            None => error(".$ref", "expected a reference to a named definition"),
+        };
+    }
+
+    let types: Vec<&str> = match schema.get("type") {
+        Some(serde_json::Value::String(ty)) => vec![ty.as_str()],
+        Some(serde_json::Value::Array(types)) if types.iter().all(|t| t.is_string()) => {
+            types.iter().filter_map(|t| t.as_str()).collect()
+        }
+        Some(_) => return error(".type", "expected a type name or an array of type names"),
+        None => return error("", "expected a type or a $ref"),
+    };
+    let nullable = types.contains(&"null");
+    let types: Vec<&str> = types.into_iter().filter(|ty| ty != &"null").collect();
+    let ty = match types.as_slice() {
+        [ty] => *ty,
+        _ => {
+            return error(
+                ".type",
+                "only a single type, optionally with null, is supported",
+            )
+        }
+    };
+
+    let format = schema.get("format").and_then(|f| f.as_str());
+    let ty: Type = match ty {
+        "string" => parse_quote! { String },
+        "boolean" => parse_quote! { bool },
+        "integer" => match format {
+            Some("int8") => parse_quote! { i8 },
+            Some("int16") => parse_quote! { i16 },
+            Some("int32") => parse_quote! { i32 },
+            Some("uint8") => parse_quote! { u8 },
+            Some("uint16") => parse_quote! { u16 },
+            Some("uint32") => parse_quote! { u32 },
+            Some("uint64") => parse_quote! { u64 },
+            _ => parse_quote! { i64 },
+        },
+        "number" => match format {
+            Some("float") => parse_quote! { f32 },
+            _ => parse_quote! { f64 },
+        },
+        "array" => match schema.get("items") {
+            Some(items) => {
+                let (item, item_nullable) = schema_type(items, &[json_path, ".items"].concat())?;
+                if item_nullable {
+                    parse_quote! { Vec<Option<#item>> }
+                } else {
+                    parse_quote! { Vec<#item> }
+                }
+            }
+            None => return error(".items", "arrays need the schema of their items"),
+        },
+        // only maps are supported, since nested structs would need a name
+        "object" => match schema.get("additionalProperties") {
+            Some(values) if values.is_object() => {
+                let (value, value_nullable) =
+                    schema_type(values, &[json_path, ".additionalProperties"].concat())?;
+                if value_nullable {
+                    parse_quote! { ::std::collections::HashMap<String, Option<#value>> }
+                } else {
+                    parse_quote! { ::std::collections::HashMap<String, #value> }
+                }
+            }
+            _ => return error(
+                "",
+                "objects are only supported through $ref or as maps through additionalProperties",
+            ),
+        },
+        _ => return error(".type", &["unsupported type `", ty, "`"].concat()),
+    };
+    Ok((ty, nullable))
+}
+
+// A preset or a mixin exported through a `macro_rules!` callback, so that `generate!` invocations
+// can use it through `use path::Name;`, even from other crates
+struct Template {
+    attrs: Vec<Attribute>,
+    name: Ident,
+    definition: proc_macro2::TokenStream,
+}
+
+impl Parse for Template {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let fork = input.fork();
+        let item: TemplateItem = fork.parse()?;
+        if !fork.is_empty() {
+            return Err(fork.error("a template holds a single preset or mixin"));
+        }
+
+        Ok(Template {
+            attrs,
+            name: item.name().clone(),
+            definition: input.parse()?,
+        })
+    }
+}
+
+// Splits off the first `use path;` of the invocation, along with the rest of its tokens
+fn split_template_use(
+    input: proc_macro2::TokenStream,
+) -> Option<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
+    use proc_macro2::TokenTree;
+
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+    let start = tokens
+        .iter()
+        .position(|t| matches!(t, TokenTree::Ident(ident) if ident == "use"))?;
+    let end = start
+        + tokens[start..]
+            .iter()
+            .position(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == ';'))?;
+    let path = tokens[start + 1..end].iter().cloned().collect();
+    let rest = tokens[..start]
+        .iter()
+        .chain(tokens[end + 1..].iter())
+        .cloned()
+        .collect();
+    Some((path, rest))
+}
+
+#[proc_macro]
+pub fn template(input: TokenStream) -> TokenStream {
+    let Template {
+        attrs,
+        name,
+        definition,
+    } = parse_macro_input!(input as Template);
+
+    // the definition is spliced in front of the invocation so that it's defined before use
+    (quote! {
+        #(#attrs)*
+        macro_rules! #name {
+            ($($input:tt)*) => {
+                ::structout::generate! { #definition $($input)* }
+            };
+        }
+    })
+    .into()
+}
+
+#[proc_macro]
+pub fn generate(input: TokenStream) -> TokenStream {
+    // templates are resolved one at a time, each calling back into `generate!`
+    if let Some((path, rest)) = split_template_use(input.clone().into()) {
+        let path: Path = match syn::parse2(path) {
+            Ok(path) => path,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        return (quote! { #path! { #rest } }).into();
+    }
+
+    let input = parse_macro_input!(input as Definitions);
+
+    let mut output = proc_macro2::TokenStream::new();
+    for (definition, template_count) in input.definitions.iter() {
+        match expand(definition, &input.templates[..*template_count]) {
+            Ok(expanded) => output.extend(expanded),
+            Err(err) => output.extend(err.to_compile_error()),
+        }
+    }
+    output.into()
+}
+
+fn expand(input: &StructGen, templates: &[TemplateItem]) -> Result<proc_macro2::TokenStream> {
+    let StructGen {
+        attrs: top_level_attrs,
+        name: base_name,
+        generics: parsed_generics,
+        where_clause,
+        body,
+        conf,
+        visibility: top_level_visibility,
+        impls,
+        ..
+    } = input;
+    let (top_level_attrs, options) = parse_definition_options(top_level_attrs)?;
+
+    let (base_shape, parsed_items, where_clause): (_, Vec<Item>, _) = match body {
+        StructBody::Named {
+            fields, extensions, ..
+        } => (
+            StructShape::Named,
+            resolve_fields(fields, extensions, templates)?
+                .into_iter()
+                .map(Item::Field)
+                .collect(),
+            where_clause.as_ref(),
+        ),
+        StructBody::Tuple {
+            fields,
+            where_clause: trailing_where_clause,
+            ..
+        } => (
+            StructShape::Tuple,
+            fields.iter().cloned().map(Item::Field).collect(),
+            where_clause.as_ref().or(trailing_where_clause.as_ref()),
+        ),
+        StructBody::Enum { variants, .. } => (
+            StructShape::Enum,
+            variants.iter().cloned().map(Item::Variant).collect(),
+            where_clause.as_ref(),
+        ),
+        StructBody::File { path, .. } => (
+            StructShape::Named,
+            schema_file_fields(path)?
+                .into_iter()
+                .map(Item::Field)
+                .collect(),
+            where_clause.as_ref(),
+        ),
+    };
+    // the schema file is included so that changes to it trigger a recompilation
+    let schema_file = match body {
+        StructBody::File { path, .. } => {
+            let file = schema_file_path(path);
+            let file = file.to_string_lossy();
+            quote! { const _: &[u8] = include_bytes!(#file); }
+        }
+        _ => quote! {},
+    };
+
+    if base_shape == StructShape::Enum {
+        if let Some(path) = options
+            .field_info
+            .as_ref()
+            .or(options.visit_fields.as_ref())
+            .or(options.has_field.as_ref().map(|(path, _)| path))
+            .or(options.common_traits.first().map(|(path, _)| path))
+            .or(options.any.as_ref().map(|(path, _)| path))
+            .or(options.field_matrix.as_ref().map(|(path, _)| path))
+        {
+            return Err(syn::Error::new_spanned(
+                path,
+                format!(
+                    "{} is not supported for enum bases",
+                    path.get_ident().unwrap()
+                ),
+            ));
+        }
+    }
+
+    if let Some((path, None)) = &options.field_matrix {
+        if base_name.is_none() {
+            return Err(syn::Error::new_spanned(
+                path,
+                "field_matrix needs a named base, or a module to document through field_matrix(module_name)",
+            ));
+        }
+    }
+
+    let mut structs: Vec<(String, StructOutputConfiguration)> = Vec::new();
+    if let Some(base_name) = base_name {
+        structs.push((
+            base_name.to_string(),
+            StructOutputConfiguration {
+                attributes: top_level_attrs.iter().collect(),
+                field_info: options.field_info.is_some(),
+                visit_fields: options.visit_fields.is_some(),
+                common_traits: options.common_traits.iter().map(|(_, t)| t).collect(),
+                ..Default::default()
+            },
+        ));
+    }
+    for c in resolve_configuration(conf, templates)? {
+        let mut omitted_fields = LinkedHashSet::<String>::new();
+        let mut included_fields = LinkedHashSet::<String>::new();
+        let mut omitted_types = Vec::<&Type>::new();
+        let mut included_types = Vec::<&Type>::new();
+        let mut omitted_tags = LinkedHashSet::<String>::new();
+        let mut included_tags = LinkedHashSet::<String>::new();
+        let mut upsert_fields = Vec::<&UpsertItem>::new();
+        let mut upsert_fields_names = LinkedHashSet::<String>::new();
+        let mut inherited_attributes: Vec<&Attribute> = top_level_attrs.iter().collect();
+        let mut stripped_attributes = Vec::<&Meta>::new();
+        let mut attributes = Vec::<&Attribute>::new();
+        let mut derives = Vec::<&Path>::new();
+        let mut underives = Vec::<&Path>::new();
+        let mut docs = Vec::<&LitStr>::new();
+        let mut cfgs = Vec::<&proc_macro2::TokenStream>::new();
+        let mut conditional_attributes = Vec::<&AttrIf>::new();
+        let mut conditional_item_attributes = Vec::<&FieldAttrIf>::new();
+        let mut item_attributes = Vec::<&FieldAttr>::new();
+        let mut stripped_item_attributes = Vec::<&Path>::new();
+        let mut shape = None;
+        let mut names = None;
+        let mut visibility = None;
+        let mut field_visibilities = Vec::<&FieldVisibility>::new();
+        let mut field_info = options.field_info.is_some();
+        let mut visit_fields = options.visit_fields.is_some();
+        let mut common_traits: Vec<&CommonTrait> =
+            options.common_traits.iter().map(|(_, t)| t).collect();
+
+        for a in c.actions.iter() {
+            let unsupported = match (&a.fields, base_shape) {
+                (ActionVariant::Upsert(items), StructShape::Enum)
+                    if items.iter().any(|u| matches!(u.item, Item::Field(_))) =>
+                {
+                    Some("fields can only be upserted into struct bases".to_string())
+                }
+                (ActionVariant::Upsert(items), StructShape::Named | StructShape::Tuple)
+                    if items.iter().any(|u| matches!(u.item, Item::Variant(_))) =>
+                {
+                    Some("variants can only be upserted into enum bases".to_string())
+                }
+                (
+                    ActionVariant::AsTuple
+                    | ActionVariant::AsUnit
+                    | ActionVariant::AsNamed(_)
+                    | ActionVariant::AsFieldEnum
+                    | ActionVariant::AsFieldNameEnum
+                    | ActionVariant::FieldInfo
+                    | ActionVariant::VisitFields
+                    | ActionVariant::CommonTrait(_)
+                    | ActionVariant::FieldVis(_),
+                    StructShape::Enum,
+                ) => Some(format!("{} is not supported for enum bases", a.name)),
+                _ => None,
+            };
+            if let Some(message) = unsupported {
+                return Err(syn::Error::new_spanned(&a.name, message));
+            }
+
+            match &a.fields {
+                ActionVariant::Omit(fields) => {
+                    omitted_fields.extend(fields.iter().map(member_name));
+                }
+                ActionVariant::Include(fields) => {
+                    included_fields.extend(fields.iter().map(member_name));
+                }
+                ActionVariant::OmitType(types) => {
+                    omitted_types.extend(types.iter());
+                }
+                ActionVariant::IncludeType(types) => {
+                    included_types.extend(types.iter());
+                }
+                ActionVariant::OmitTagged(tags) => {
+                    omitted_tags.extend(tags.iter().map(|t| t.to_string()));
+                }
+                ActionVariant::IncludeTagged(tags) => {
+                    included_tags.extend(tags.iter().map(|t| t.to_string()));
+                }
+                ActionVariant::Attr(attrs) => {
+                    attributes.extend(attrs.iter());
+                }
+                ActionVariant::Upsert(fields) => {
+                    upsert_fields_names.extend(fields.iter().map(|f| member_name(&f.member)));
+                    upsert_fields.extend(fields);
+                }
+                ActionVariant::AsTuple => {
+                    shape = Some(StructShape::Tuple);
+                }
+                ActionVariant::AsUnit => {
+                    shape = Some(StructShape::Unit);
+                }
+                ActionVariant::AsFieldEnum => {
+                    shape = Some(StructShape::FieldEnum);
+                }
+                ActionVariant::AsFieldNameEnum => {
+                    shape = Some(StructShape::FieldNameEnum);
+                }
+                ActionVariant::FieldInfo => {
+                    field_info = true;
+                }
+                ActionVariant::VisitFields => {
+                    visit_fields = true;
+                }
+                ActionVariant::CommonTrait(common_trait) => {
+                    common_traits.push(common_trait);
+                }
+                ActionVariant::AttrIf(attr_if) => {
+                    conditional_attributes.push(attr_if);
+                }
+                ActionVariant::FieldAttrIf(field_attr_if) => {
+                    conditional_item_attributes.push(field_attr_if);
+                }
+                ActionVariant::FieldAttr(field_attr) => {
+                    item_attributes.push(field_attr);
+                }
+                ActionVariant::StripFieldAttrs(paths) => {
+                    stripped_item_attributes.extend(paths.iter());
+                }
+                ActionVariant::NoInherit => {
+                    inherited_attributes.clear();
+                }
+                ActionVariant::StripAttr(metas) => {
+                    stripped_attributes.extend(metas.iter());
+                }
+                ActionVariant::Derive(paths) => {
+                    derives.extend(paths.iter());
+                }
+                ActionVariant::Underive(paths) => {
+                    underives.extend(paths.iter());
+                }
+                ActionVariant::Doc(doc) => {
+                    docs.push(doc);
+                }
+                ActionVariant::Cfg(cfg) => {
+                    cfgs.push(cfg);
+                }
+                ActionVariant::AsNamed(fields_names) => {
+                    shape = Some(StructShape::Named);
+                    names = Some(fields_names);
+                }
+                ActionVariant::Vis(vis) => {
+                    visibility = Some(vis);
+                }
+                ActionVariant::FieldVis(field_vis) => {
+                    field_visibilities.push(field_vis);
+                }
+            }
+        }
+
+        // a unit struct would drop the upserted fields
+        if shape == Some(StructShape::Unit) && !upsert_fields.is_empty() {
+            let upsert = c
+                .actions
+                .iter()
+                .find(|a| matches!(a.fields, ActionVariant::Upsert(_)))
+                .unwrap();
+            return Err(syn::Error::new_spanned(
+                &upsert.name,
+                "upsert is not supported for unit structs",
+            ));
+        }
+
+        structs.push((
+            c.struct_name.resolve(base_name.as_ref())?.to_string(),
+            StructOutputConfiguration {
+                omitted_fields,
+                included_fields,
+                omitted_types,
+                included_types,
+                omitted_tags,
+                included_tags,
+                upsert_fields,
+                upsert_fields_names,
+                inherited_attributes,
+                stripped_attributes,
+                attributes,
+                derives,
+                underives,
+                docs,
+                cfgs,
+                auto_doc: options.no_auto_doc.is_none(),
+                conditional_attributes,
+                conditional_item_attributes,
+                item_attributes,
+                stripped_item_attributes,
+                shape,
+                names,
+                visibility,
+                field_visibilities,
+                field_info,
+                visit_fields,
+                common_traits,
+            },
+        ));
+    }
+
+    let generics: Vec<TypeArgumentConfiguration> = if let Some(parsed_generics) = &parsed_generics {
+        parsed_generics
+            .args
+            .iter()
+            .map(|arg| {
+                let mut collector = TypeArgumentsCollectorVisitor {
+                    ..Default::default()
+                };
+                collector.visit_generic_argument(arg);
+
+                TypeArgumentConfiguration {
+                    arg,
+                    identifiers: collector.items,
+                }
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let wheres: Vec<(&WherePredicate, Vec<&TypeArgumentConfiguration>)> =
+        if let Some(where_clause) = where_clause {
+            where_clause
+                .predicates
+                .iter()
+                .map(|p| {
+                    let mut collector = TypeArgumentsCheckVisitor {
+                        args: &generics,
+                        matched: Vec::new(),
+                    };
+                    collector.visit_where_predicate(p);
+
+                    (p, collector.matched)
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+    let items: Vec<ItemConfiguration> = parsed_items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let (output, tags) = parse_item_tags(item)?;
+
+            Ok(ItemConfiguration {
+                name: match item {
+                    Item::Field(Field {
+                        ident: Some(ident), ..
+                    }) => ident.to_string(),
+                    Item::Field(_) => i.to_string(),
+                    Item::Variant(variant) => variant.ident.to_string(),
+                },
+                item: output,
+                tags,
+            })
+        })
+        .collect::<Result<_>>()?;
+
+    let mut outputs: Vec<OutputStruct> = structs
+        .iter()
+        .map(
+            |(
+                struct_name,
+                StructOutputConfiguration {
+                    omitted_fields,
+                    inherited_attributes,
+                    stripped_attributes,
+                    attributes,
+                    derives,
+                    underives,
+                    docs,
+                    cfgs,
+                    auto_doc,
+                    conditional_attributes,
+                    conditional_item_attributes,
+                    item_attributes,
+                    stripped_item_attributes,
+                    included_fields,
+                    omitted_types,
+                    included_types,
+                    omitted_tags,
+                    included_tags,
+                    upsert_fields,
+                    upsert_fields_names,
+                    shape,
+                    names,
+                    visibility,
+                    field_visibilities,
+                    field_info,
+                    visit_fields,
+                    common_traits,
+                },
+            )| {
+                let name = Ident::new(struct_name, Span::call_site());
+                let shape = shape.unwrap_or(base_shape);
+                let mut used_items = Vec::<(String, Item)>::new();
+                let mut used_generics = LinkedHashSet::<&GenericArgument>::new();
+                let mut used_wheres = LinkedHashSet::<&WherePredicate>::new();
+
+                let matches_type = |types: &Vec<&Type>, i: &ItemConfiguration| {
+                    i.item
+                        .types()
+                        .iter()
+                        .any(|ty| types.iter().any(|pattern| type_matches(pattern, ty)))
+                };
+                let is_omitted = |i: &ItemConfiguration| {
+                    omitted_fields.contains(&i.name)
+                        || matches_type(omitted_types, i)
+                        || i.tags.iter().any(|tag| omitted_tags.contains(tag))
+                };
+                let is_included = |i: &ItemConfiguration| {
+                    included_fields.contains(&i.name)
+                        || matches_type(included_types, i)
+                        || i.tags.iter().any(|tag| included_tags.contains(tag))
+                };
+
+                let test_skip_predicate: Box<dyn Fn(&ItemConfiguration) -> bool> =
+                    if included_fields.is_empty()
+                        && included_types.is_empty()
+                        && included_tags.is_empty()
+                    {
+                        Box::new(|i: &ItemConfiguration| {
+                            upsert_fields_names.contains(&i.name) || is_omitted(i)
+                        })
+                    } else {
+                        Box::new(|i: &ItemConfiguration| {
+                            upsert_fields_names.contains(&i.name) || !is_included(i)
+                        })
+                    };
+
+                if shape != StructShape::Unit {
+                    for i in items.iter() {
+                        // unnamed fields are upserted in place so that the following indexes are
+                        // preserved
+                        if base_shape == StructShape::Tuple {
+                            if let Some(upsert) = upsert_fields
+                                .iter()
+                                .find(|u| member_name(&u.member) == i.name)
+                            {
+                                used_items.push((i.name.clone(), upsert.item.clone()));
+                                continue;
+                            }
+                        }
+
+                        if test_skip_predicate(i) {
+                            continue;
+                        }
+
+                        used_items.push((i.name.clone(), i.item.clone()));
+                    }
+
+                    used_items.extend(
+                        upsert_fields
+                            .iter()
+                            .map(|u| (member_name(&u.member), u.item.clone()))
+                            .filter(|(name, _)| {
+                                base_shape != StructShape::Tuple
+                                    || !items.iter().any(|i| &i.name == name)
+                            }),
+                    );
+                }
+
+                let mut field_items = Vec::<Field>::new();
+                let mut variant_items = Vec::<Variant>::new();
+                let insert_item_attributes = |attrs: &mut Vec<Attribute>, item_name: &str| {
+                    attrs.retain(|attr| !stripped_item_attributes.contains(&&attr.path));
+                    for item_attrs in item_attributes.iter() {
+                        let applies = match &item_attrs.field {
+                            Some(member) => member_name(member) == item_name,
+                            None => true,
+                        };
+                        if applies {
+                            attrs.extend(item_attrs.attrs.iter().cloned());
+                        }
+                    }
+                };
+                let omitted_items: Vec<&String> = items
+                    .iter()
+                    .map(|i| &i.name)
+                    .filter(|item_name| !used_items.iter().any(|(name, _)| &name == item_name))
+                    .collect();
+                // the items are named as in the base, e.g. by their index in a tuple base
+                let mut item_names = Vec::<String>::new();
+                for (item_name, item) in used_items.into_iter() {
+                    item_names.push(item_name.clone());
+                    match item {
+                        Item::Field(mut field) => {
+                            // the last matching field_vis wins
+                            if let Some(field_vis) = field_visibilities.iter().rev().find(|fv| {
+                                fv.fields.is_empty()
+                                    || fv
+                                        .fields
+                                        .iter()
+                                        .any(|member| member_name(member) == item_name)
+                            }) {
+                                field.vis = field_vis.visibility.clone();
+                            }
+                            // the attributes of named fields don't necessarily apply to unnamed
+                            // ones, unlike the conditional compilation ones
+                            if (shape == StructShape::Tuple && base_shape == StructShape::Named)
+                                || shape == StructShape::FieldEnum
+                                || shape == StructShape::FieldNameEnum
+                            {
+                                field.attrs = field
+                                    .attrs
+                                    .iter()
+                                    .filter_map(|attr| {
+                                        let meta = cfg_meta(&attr.parse_meta().ok()?)?;
+                                        Some(parse_quote! { #[#meta] })
+                                    })
+                                    .collect();
+                            }
+                            insert_item_attributes(&mut field.attrs, &item_name);
+                            field_items.push(field);
+                        }
+                        Item::Variant(mut variant) => {
+                            insert_item_attributes(&mut variant.attrs, &item_name);
+                            variant_items.push(variant);
+                        }
+                    }
+                }
+
+                if let Some(names) = names {
+                    if names.len() != field_items.len() {
+                        return Err(syn::Error::new_spanned(
+                            names,
+                            format!(
+                                "{} has {} fields, but {} names were given",
+                                name,
+                                field_items.len(),
+                                names.len()
+                            ),
+                        ));
+                    }
+                    for (field, ident) in field_items.iter_mut().zip(names.iter()) {
+                        field.ident = Some(ident.clone());
+                        field.colon_token = Some(Default::default());
+                    }
+                } else if shape == StructShape::Named
+                    && field_items.iter().any(|f| f.ident.is_none())
+                {
+                    return Err(syn::Error::new_spanned(
+                        &name,
+                        format!(
+                            "the fields of {} have to be named through as_named(...)",
+                            name
+                        ),
+                    ));
+                }
+
+                let mut attributes: Vec<Attribute> = inherited_attributes
+                    .iter()
+                    .filter_map(|attr| {
+                        stripped_attributes
+                            .iter()
+                            .try_fold((*attr).clone(), |attr, strip| strip_attribute(attr, strip))
+                    })
+                    .chain(attributes.iter().copied().cloned())
+                    .chain(cfgs.iter().map(|cfg| -> Attribute {
+                        parse_quote! { #[cfg(#cfg)] }
+                    }))
+                    .collect();
+                for attr_if in conditional_attributes.iter() {
+                    if attr_if.condition.holds(&item_names) {
+                        attributes.extend(attr_if.attrs.iter().cloned());
+                    }
+                }
+                let mut attributes = merge_derives(attributes, derives, underives);
+                for field_attr_if in conditional_item_attributes.iter() {
+                    if !field_attr_if.condition.holds(&item_names) {
+                        continue;
+                    }
+                    let target = member_name(&field_attr_if.field);
+                    if let Some(i) = item_names.iter().position(|name| name == &target) {
+                        let attrs = if i < field_items.len() {
+                            &mut field_items[i].attrs
+                        } else {
+                            &mut variant_items[i - field_items.len()].attrs
+                        };
+                        attrs.extend(field_attr_if.attrs.iter().cloned());
+                    }
+                }
+
+                // a field name enum doesn't hold any values
+                let used_types = field_items
+                    .iter()
+                    .filter(|_| shape != StructShape::FieldNameEnum)
+                    .map(|f| &f.ty)
+                    .chain(
+                        variant_items
+                            .iter()
+                            .flat_map(|v| v.fields.iter().map(|f| &f.ty)),
+                    );
+                for ty in used_types {
+                    let mut collector = TypeArgumentsCheckVisitor {
+                        args: &generics,
+                        matched: Vec::new(),
+                    };
+                    collector.visit_type(ty);
+
+                    for type_arg in collector.matched {
+                        used_generics.insert(type_arg.arg);
+
+                        for w in wheres.iter() {
+                            for w_type_arg in w.1.iter() {
+                                if w_type_arg.arg == type_arg.arg {
+                                    used_wheres.insert(w.0);
+                                }
+                            }
+                        }
+                    }
+                }
+
+                let shape = if (shape == StructShape::Named || shape == StructShape::Tuple)
+                    && field_items.is_empty()
+                {
+                    StructShape::Unit
+                } else {
+                    shape
+                };
+
+                let mut doc_lines: Vec<String> = docs.iter().map(|doc| doc.value()).collect();
+                if *auto_doc {
+                    if !doc_lines.is_empty() {
+                        doc_lines.push(String::new());
+                    }
+                    doc_lines.push(match base_name {
+                        Some(base_name) => format!("Generated from [`{}`].", base_name),
+                        None => "Generated through `structout::generate!`.".to_string(),
+                    });
+                    let item_kind = if base_shape == StructShape::Enum {
+                        "variants"
+                    } else {
+                        "fields"
+                    };
+                    let list = |names: Vec<&String>| {
+                        names
+                            .iter()
+                            .map(|name| format!("`{}`", name.trim_start_matches("r#")))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    };
+                    let mut notes = Vec::new();
+                    if !omitted_items.is_empty() {
+                        notes.push(format!("- Omitted {}: {}.", item_kind, list(omitted_items)));
+                    }
+                    if !upsert_fields_names.is_empty() {
+                        notes.push(format!(
+                            "- Upserted {}: {}.",
+                            item_kind,
+                            list(upsert_fields_names.iter().collect())
+                        ));
+                    }
+                    if shape != base_shape {
+                        notes.push(format!("- Output as {}.", shape.description()));
+                    }
+                    if !notes.is_empty() {
+                        doc_lines.push(String::new());
+                        doc_lines.extend(notes);
+                    }
+                }
+                // the inherited docs come first, in their own paragraph
+                if !doc_lines.is_empty() && attributes.iter().any(|a| a.path.is_ident("doc")) {
+                    doc_lines.insert(0, String::new());
+                }
+                attributes.extend(doc_lines.iter().map(|line| -> Attribute {
+                    let line = if line.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" {}", line)
+                    };
+                    parse_quote! { #[doc = #line] }
+                }));
+
+                Ok(OutputStruct {
+                    name,
+                    attributes,
+                    visibility: visibility.or(top_level_visibility.as_ref()),
+                    shape,
+                    fields: field_items,
+                    variants: variant_items,
+                    generics: Vec::from_iter(used_generics),
+                    wheres: Vec::from_iter(used_wheres),
+                    field_info: *field_info,
+                    visit_fields: *visit_fields,
+                    common_traits: common_traits.clone(),
+                })
+            },
+        )
+        .collect::<Result<_>>()?;
+
+    let field_matrix_module = options.field_matrix.as_ref().map(|(_, module)| {
+        match module {
+            Some(module) => {
+                let matrix = field_matrix(&outputs, Some("super::"));
+                quote! {
+                #(#[doc = #matrix])*
+                #top_level_visibility mod #module {}
+                }
+            }
+            // the base is the first output when it's named
+            None => {
+                let matrix = field_matrix(&outputs, None);
+                let base = &mut outputs[0];
+                if base.attributes.iter().any(|a| a.path.is_ident("doc")) {
+                    base.attributes.push(parse_quote! { #[doc = ""] });
+                }
+                base.attributes
+                    .extend(matrix.iter().map(|line| -> Attribute {
+                        parse_quote! { #[doc = #line] }
+                    }));
+                quote! {}
+            }
+        }
+    });
+
+    for output in outputs
+        .iter()
+        .filter(|o| matches!(o.shape, StructShape::FieldEnum | StructShape::FieldNameEnum))
+    {
+        output.check_field_variants()?;
+    }
+
+    // tuple structs are generated once per combination of the predicates of their fields
+    for output in outputs.iter().filter(|o| o.shape == StructShape::Tuple) {
+        if output.field_cfg_predicates(output.fields.len()).len() > MAX_TUPLE_FIELD_PREDICATES {
+            return Err(syn::Error::new_spanned(
+                &output.name,
+                format!(
+                    "the fields of {} are gated behind more than {} distinct predicates",
+                    output.name, MAX_TUPLE_FIELD_PREDICATES
+                ),
+            ));
+        }
+    }
+
+    let token_streams = outputs.iter().map(OutputStruct::definition);
+    let tuple_accessors = outputs
+        .iter()
+        .filter(|o| {
+            o.shape == StructShape::Tuple
+                && (0..o.fields.len()).any(|i| o.fields[i].ident.is_some() || o.field_shifts(i))
+        })
+        .map(OutputStruct::tuple_accessors);
+
+    let tuple_conversions = outputs
+        .iter()
+        .filter(|o| o.shape == StructShape::Tuple)
+        .flat_map(|tuple| {
+            outputs
+                .iter()
+                .filter(move |o| o.shape == StructShape::Named && o.has_same_fields(tuple))
+                .map(move |named| tuple.conversions(named))
+        });
+
+    // the base is the first output when it's named
+    let field_enums = outputs
+        .iter()
+        .enumerate()
+        .filter(|(_, o)| o.shape == StructShape::FieldEnum)
+        .map(|(i, field_enum)| {
+            let base = outputs
+                .first()
+                .filter(|_| i > 0 && base_name.is_some() && base_shape == StructShape::Named);
+            field_enum.field_enum_methods(base)
+        });
+
+    let structs: Vec<&OutputStruct> = outputs
+        .iter()
+        .filter(|o| {
+            matches!(
+                o.shape,
+                StructShape::Named | StructShape::Tuple | StructShape::Unit
+            )
+        })
+        .collect();
+    // only named fields can be looked up across the generated structs
+    let field_name_enums = outputs
+        .iter()
+        .filter(|o| o.shape == StructShape::FieldNameEnum)
+        .map(|o| {
+            o.field_name_enum_methods(
+                Some(&structs[..]).filter(|_| base_shape == StructShape::Named),
+            )
+        });
+
+    let field_infos = outputs
+        .iter()
+        .filter(|o| o.field_info)
+        .map(OutputStruct::field_info);
+
+    let field_visitors = outputs
+        .iter()
+        .filter(|o| {
+            o.visit_fields
+                && matches!(
+                    o.shape,
+                    StructShape::Named | StructShape::Tuple | StructShape::Unit
+                )
+        })
+        .map(OutputStruct::visit_fields);
+
+    let struct_outputs: Vec<&OutputStruct> = outputs
+        .iter()
+        .filter(|o| o.shape == StructShape::Named || o.shape == StructShape::Tuple)
+        .collect();
+
+    let has_field = options.has_field.as_ref().map(|(_, module)| {
+        let mut markers = Vec::<&Ident>::new();
+        for o in struct_outputs.iter() {
+            for ident in o.fields.iter().filter_map(|f| f.ident.as_ref()) {
+                if !markers.contains(&ident) {
+                    markers.push(ident);
+                }
+            }
+        }
+        let impls = struct_outputs.iter().map(|o| o.has_field_impls(module));
+
+        quote! {
+            #top_level_visibility mod #module {
+                #(
+                    #[allow(non_camel_case_types)]
+                    pub struct #markers;
+                )*
+            }
+            #(#impls)*
+        }
+    });
+
+    let any = options.any.as_ref().map(|(_, any_name)| {
+        let members = &structs;
+        let variants = members.iter().map(|m| {
+            let member_name = &m.name;
+            let member_type_generics = m.type_generics();
+            let member_cfg = m.cfg();
+            let variant: Variant =
+                parse_quote! { #member_cfg #member_name(#member_name #member_type_generics) };
+            variant
+        });
+        let any = OutputStruct {
+            name: any_name.clone(),
+            // the enum goes away along with all of the structs
+            attributes: shared_cfg_attributes(members),
+            visibility: top_level_visibility.as_ref(),
+            shape: StructShape::Enum,
+            fields: Vec::new(),
+            variants: variants.collect(),
+            // the generics and constraints of all the structs, in the order of the base
+            generics: generics
+                .iter()
+                .map(|g| g.arg)
+                .filter(|arg| members.iter().any(|m| m.generics.contains(arg)))
+                .collect(),
+            wheres: wheres
+                .iter()
+                .map(|w| w.0)
+                .filter(|w| members.iter().any(|m| m.wheres.contains(w)))
+                .collect(),
+            field_info: false,
+            visit_fields: false,
+            common_traits: Vec::new(),
+        };
+
+        let definition = any.definition();
+        let methods = any.any_methods(members);
+        quote! {
+            #definition
+            #methods
+        }
+    });
+
+    let mut impl_templates = Vec::new();
+    for template in impls.iter() {
+        match &template.targets {
+            Some(targets) => {
+                for target in targets.iter() {
+                    let output = match structs.iter().find(|o| &o.name == target) {
+                        Some(output) => output,
+                        None => {
+                            return Err(syn::Error::new_spanned(
+                                target,
+                                format!("{} is not a struct generated by this definition", target),
+                            ))
+                        }
+                    };
+                    for item in template.items.iter() {
+                        if let Some(field) = output.missing_fields(item).first() {
+                            return Err(syn::Error::new_spanned(
+                                field,
+                                format!("{} has no field {}", target, member_name(field)),
+                            ));
+                        }
+                    }
+                    impl_templates.push(output.impl_template(template));
+                }
+            }
+            None => impl_templates.extend(structs.iter().map(|o| o.impl_template(template))),
+        }
+    }
+
+    let mut common_trait_names = Vec::<&Ident>::new();
+    for o in struct_outputs.iter() {
+        for common_trait in o.common_traits.iter() {
+            if !common_trait_names.contains(&&common_trait.name) {
+                common_trait_names.push(&common_trait.name);
+            }
+        }
+    }
+    let common_traits = common_trait_names.into_iter().map(|trait_name| {
+        let implementors: Vec<&OutputStruct> = struct_outputs
+            .iter()
+            .copied()
+            .filter(|o| o.common_traits.iter().any(|t| &t.name == trait_name))
+            .collect();
+        let setters = implementors.iter().any(|o| {
+            o.common_traits
+                .iter()
+                .any(|t| &t.name == trait_name && t.setters.is_some())
+        });
+        // fields are common when all implementors have them with the same type
+        let fields: Vec<&Field> = implementors[0]
+            .fields
+            .iter()
+            .filter(|f| f.ident.is_some() && implementors.iter().all(|o| o.has_field(f)))
+            .collect();
+
+        let mut used_identifiers = TypeArgumentsCollectorVisitor::default();
+        for f in fields.iter() {
+            used_identifiers.visit_type(&f.ty);
+        }
+        let trait_generics: Vec<proc_macro2::TokenStream> = implementors[0]
+            .generics
+            .iter()
+            .filter(|arg| {
+                let mut collector = TypeArgumentsCollectorVisitor::default();
+                collector.visit_generic_argument(arg);
+                collector
+                    .items
+                    .iter()
+                    .any(|id| used_identifiers.items.contains(id))
+            })
+            .map(|arg| generic_name(arg))
+            .collect();
+        let common_trait = if trait_generics.is_empty() {
+            quote! { #trait_name }
+        } else {
+            quote! { #trait_name<#(#trait_generics),*> }
+        };
+
+        let methods = fields.iter().map(|field| {
+            let getter = field.ident.as_ref().unwrap();
+            let ty = &field.ty;
+            let gate = cfg_attributes(&field.attrs);
+            let setter = if setters {
+                let setter = setter_ident(getter);
+                quote! { #gate fn #setter(&mut self, value: #ty); }
+            } else {
+                quote! {}
+            };
+            quote! {
+                #gate
+                fn #getter(&self) -> &#ty;
+                #setter
+            }
+        });
+        let impls = implementors
+            .iter()
+            .map(|o| o.common_trait_impl(&common_trait, &fields, setters));
+        let cfg = shared_cfg_attributes(&implementors);
+
+        quote! {
+            #(#cfg)*
+            #top_level_visibility trait #common_trait {
+                #(#methods)*
+            }
+            #(#impls)*
+        }
+    });
+
+    let enum_conversions = outputs
+        .first()
+        .filter(|_| base_name.is_some() && base_shape == StructShape::Enum)
+        .map(|full| {
+            outputs
+                .iter()
+                .skip(1)
+                .filter(move |subset| subset.is_subset_of(full))
+                .map(move |subset| subset.enum_conversions(full))
+        })
+        .into_iter()
+        .flatten();
+
+    Ok(quote! {
+       #schema_file
+       #(#token_streams)*
+       #(#tuple_accessors)*
+       #(#tuple_conversions)*
+       #(#enum_conversions)*
+       #(#field_enums)*
+       #(#field_name_enums)*
+       #(#field_infos)*
+       #(#field_visitors)*
+       #has_field
+       #(#common_traits)*
+       #any
+       #(#impl_templates)*
+       #field_matrix_module
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use path_clean::PathClean;
+    use std::env;
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    pub fn absolute_path(path: impl AsRef<Path>) -> std::io::Result<PathBuf> {
+        let path = path.as_ref();
+
+        let absolute_path = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            env::current_dir()?.join(path)
+        }
+        .clean();
+
+        Ok(absolute_path)
+    }
+
+    fn run_for_fixture(fixture: &str) -> String {
+        let output = Command::new("cargo")
+            .arg("expand")
+            .arg(fixture)
+            .arg("--manifest-path")
+            .arg(format!(
+                "{}",
+                absolute_path("./test_fixtures/testbed/Cargo.toml")
+                    .unwrap()
+                    .display()
+            ))
+            .output()
+            .expect("Failed to spawn process");
+
+        String::from_utf8_lossy(&output.stdout).to_string()
+    }
+
+    #[test]
+    fn generics() {
+        insta::assert_snapshot!(run_for_fixture("generics"), @r###"
         pub mod generics {
             use structout::generate;
-            struct OnlyBar<T> {
-                bar: T,
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `foo`."]
+            struct OnlyBar<T> {
+                bar: T,
+            }
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `bar`."]
+            struct OnlyFoo {
+                foo: u32,
+            }
+        }
+        "###);
+    }
+
+    #[test]
+    fn wheres() {
+        insta::assert_snapshot!(run_for_fixture("wheres"), @r###"
+        pub mod wheres {
+            use structout::generate;
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `foo`."]
+            struct OnlyBar<C>
+            where
+                C: Copy,
+            {
+                bar: C,
+            }
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `bar`."]
+            struct OnlyFoo<S>
+            where
+                S: Sized,
+            {
+                foo: S,
+            }
+        }
+        "###);
+    }
+
+    #[test]
+    fn simple() {
+        insta::assert_snapshot!(run_for_fixture("simple"), @r###"
+        pub mod simple {
+            use structout::generate;
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `foo`."]
+            struct WithoutFoo {
+                bar: u64,
+                baz: String,
+            }
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `bar`."]
+            struct WithoutBar {
+                foo: u32,
+                baz: String,
+            }
+            # [object (context = Database)]
+            #[object(config = "latest")]
+            #[doc = " Generated through `structout::generate!`."]
+            struct WithAttrs {
+                foo: u32,
+                bar: u64,
+                baz: String,
+            }
+        }
+        "###);
+    }
+
+    #[test]
+    fn visibility() {
+        insta::assert_snapshot!(run_for_fixture("visibility"), @r###"
+        pub mod visibility {
+            use structout::generate;
+            #[doc = " Generated through `structout::generate!`."]
+            pub(crate) struct Everything {
+                foo: u32,
+                pub(crate) bar: u64,
+            }
+            #[doc = " Generated through `structout::generate!`."]
+            struct Private {
+                foo: u32,
+                pub(crate) bar: u64,
+            }
+            #[doc = " Generated through `structout::generate!`."]
+            pub struct Public {
+                pub foo: u32,
+                pub bar: u64,
+            }
+            #[doc = " Generated through `structout::generate!`."]
+            pub(crate) struct OnlyFooPublic {
+                pub foo: u32,
+                pub(crate) bar: u64,
+            }
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Output as a tuple struct."]
+            pub(crate) struct Tupled(pub(super) u32, pub(crate) u64);
+            impl Tupled {
+                pub(super) const FOO_INDEX: usize = 0;
+                pub(super) fn foo(&self) -> &u32 {
+                    &self.0
+                }
+                pub(super) fn foo_mut(&mut self) -> &mut u32 {
+                    &mut self.0
+                }
+                pub(crate) const BAR_INDEX: usize = 1;
+                pub(crate) fn bar(&self) -> &u64 {
+                    &self.1
+                }
+                pub(crate) fn bar_mut(&mut self) -> &mut u64 {
+                    &mut self.1
+                }
+            }
+            impl ::core::convert::From<Tupled> for Everything {
+                fn from(value: Tupled) -> Self {
+                    Everything {
+                        foo: value.0,
+                        bar: value.1,
+                    }
+                }
+            }
+            impl ::core::convert::From<Everything> for Tupled {
+                fn from(value: Everything) -> Self {
+                    Tupled(value.foo, value.bar)
+                }
+            }
+            impl ::core::convert::From<Tupled> for Private {
+                fn from(value: Tupled) -> Self {
+                    Private {
+                        foo: value.0,
+                        bar: value.1,
+                    }
+                }
+            }
+            impl ::core::convert::From<Private> for Tupled {
+                fn from(value: Private) -> Self {
+                    Tupled(value.foo, value.bar)
+                }
+            }
+            impl ::core::convert::From<Tupled> for Public {
+                fn from(value: Tupled) -> Self {
+                    Public {
+                        foo: value.0,
+                        bar: value.1,
+                    }
+                }
+            }
+            impl ::core::convert::From<Public> for Tupled {
+                fn from(value: Public) -> Self {
+                    Tupled(value.foo, value.bar)
+                }
+            }
+            impl ::core::convert::From<Tupled> for OnlyFooPublic {
+                fn from(value: Tupled) -> Self {
+                    OnlyFooPublic {
+                        foo: value.0,
+                        bar: value.1,
+                    }
+                }
+            }
+            impl ::core::convert::From<OnlyFooPublic> for Tupled {
+                fn from(value: OnlyFooPublic) -> Self {
+                    Tupled(value.foo, value.bar)
+                }
+            }
+        }
+        "###);
+    }
+
+    #[test]
+    fn include() {
+        insta::assert_snapshot!(run_for_fixture("include"), @r###"
+        pub mod include {
+            use structout::generate;
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `foo`."]
+            struct WithoutFoo {
+                bar: u64,
+            }
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `bar`."]
+            struct WithoutBar {
+                foo: u32,
+            }
+        }
+        "###);
+    }
+
+    #[test]
+    fn as_tuple() {
+        insta::assert_snapshot!(run_for_fixture("as_tuple"), @r###"
+        pub mod as_tuple {
+            use structout::generate;
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `foo`."]
+            #[doc = " - Output as a tuple struct."]
+            struct OnlyBar<C>(C, i32)
+            where
+                C: Copy;
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `bar`."]
+            #[doc = " - Output as a tuple struct."]
+            struct OnlyFoo<S>(S, i32)
+            where
+                S: Sized;
+            impl<C> OnlyBar<C>
+            where
+                C: Copy,
+            {
+                const BAR_INDEX: usize = 0;
+                fn bar(&self) -> &C {
+                    &self.0
+                }
+                fn bar_mut(&mut self) -> &mut C {
+                    &mut self.0
+                }
+                const OTHER_INDEX: usize = 1;
+                fn other(&self) -> &i32 {
+                    &self.1
+                }
+                fn other_mut(&mut self) -> &mut i32 {
+                    &mut self.1
+                }
+            }
+            impl<S> OnlyFoo<S>
+            where
+                S: Sized,
+            {
+                const FOO_INDEX: usize = 0;
+                fn foo(&self) -> &S {
+                    &self.0
+                }
+                fn foo_mut(&mut self) -> &mut S {
+                    &mut self.0
+                }
+                const OTHER_INDEX: usize = 1;
+                fn other(&self) -> &i32 {
+                    &self.1
+                }
+                fn other_mut(&mut self) -> &mut i32 {
+                    &mut self.1
+                }
+            }
+        }
+        "###);
+    }
+
+    #[test]
+    fn upsert() {
+        insta::assert_snapshot!(run_for_fixture("upsert"), @r###"
+        pub mod upsert {
+            use structout::generate;
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Upserted fields: `bar`, `baz`."]
+            struct NewFields {
+                foo: u32,
+                bar: i32,
+                baz: i64,
+            }
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Upserted fields: `foo`."]
+            struct OverriddenField {
+                foo: u64,
+            }
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Upserted fields: `foo`."]
+            #[doc = " - Output as a tuple struct."]
+            struct Tupled(u64);
+            impl Tupled {
+                const FOO_INDEX: usize = 0;
+                fn foo(&self) -> &u64 {
+                    &self.0
+                }
+                fn foo_mut(&mut self) -> &mut u64 {
+                    &mut self.0
+                }
+            }
+            impl ::core::convert::From<Tupled> for OverriddenField {
+                fn from(value: Tupled) -> Self {
+                    OverriddenField { foo: value.0 }
+                }
+            }
+            impl ::core::convert::From<OverriddenField> for Tupled {
+                fn from(value: OverriddenField) -> Self {
+                    Tupled(value.foo)
+                }
+            }
+        }
+        "###);
+    }
+
+    #[test]
+    fn shared_attrs() {
+        insta::assert_snapshot!(run_for_fixture("shared_attrs"), @r###"
+        pub mod shared_attrs {
+            use structout::generate;
+            #[doc = " Generated through `structout::generate!`."]
+            struct InheritsAttributes {
+                foo: u32,
+            }
+            #[automatically_derived]
+            #[allow(unused_qualifications)]
+            impl ::core::fmt::Debug for InheritsAttributes {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    match *self {
+                        InheritsAttributes {
+                            foo: ref __self_0_0,
+                        } => {
+                            let mut debug_trait_builder = f.debug_struct("InheritsAttributes");
+                            let _ = debug_trait_builder.field("foo", &&(*__self_0_0));
+                            debug_trait_builder.finish()
+                        }
+                    }
+                }
+            }
+            #[doc = " Generated through `structout::generate!`."]
+            struct InheritsAttributesTwo {
+                foo: u32,
+            }
+            #[automatically_derived]
+            #[allow(unused_qualifications)]
+            impl ::core::fmt::Debug for InheritsAttributesTwo {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    match *self {
+                        InheritsAttributesTwo {
+                            foo: ref __self_0_0,
+                        } => {
+                            let mut debug_trait_builder = f.debug_struct("InheritsAttributesTwo");
+                            let _ = debug_trait_builder.field("foo", &&(*__self_0_0));
+                            debug_trait_builder.finish()
+                        }
+                    }
+                }
+            }
+        }
+        "###);
+    }
+
+    #[test]
+    fn select() {
+        insta::assert_snapshot!(run_for_fixture("select"), @r###"
+        pub mod select {
+            use structout::generate;
+            struct Secret<T>(T);
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `password`."]
+            struct WithoutSecrets<T> {
+                id: u32,
+                revision: u64,
+                nickname: Option<String>,
+                parent: Option<T>,
+            }
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `id`, `revision`, `password`."]
+            struct OnlyOptionals<T> {
+                nickname: Option<String>,
+                parent: Option<T>,
+            }
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `revision`, `password`."]
+            struct WithoutInternals<T> {
+                id: u32,
+                nickname: Option<String>,
+                parent: Option<T>,
+            }
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `id`, `password`, `nickname`, `parent`."]
+            struct OnlyInternals {
+                revision: u64,
+            }
+        }
+        "###);
+    }
+
+    #[test]
+    fn unit() {
+        insta::assert_snapshot!(run_for_fixture("unit"), @r###"
+        pub mod unit {
+            use structout::generate;
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `foo`."]
+            #[doc = " - Output as a unit struct."]
+            struct Nothing;
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `foo`."]
+            #[doc = " - Output as a unit struct."]
+            struct NothingTupled;
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `foo`."]
+            #[doc = " - Output as a unit struct."]
+            struct Marker;
+        }
+        "###);
+    }
+
+    #[test]
+    fn tuple_conversions() {
+        insta::assert_snapshot!(run_for_fixture("tuple_conversions"), @r###"
+        pub mod tuple_conversions {
+            use structout::generate;
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Output as a tuple struct."]
+            struct Tupled<T>(pub u32, T)
+            where
+                T: Clone;
+            #[doc = " Generated through `structout::generate!`."]
+            struct Named<T>
+            where
+                T: Clone,
+            {
+                pub foo: u32,
+                bar: T,
+            }
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `foo`."]
+            #[doc = " - Output as a tuple struct."]
+            struct OnlyBar<T>(T)
+            where
+                T: Clone;
+            impl<T> Tupled<T>
+            where
+                T: Clone,
+            {
+                pub const FOO_INDEX: usize = 0;
+                pub fn foo(&self) -> &u32 {
+                    &self.0
+                }
+                pub fn foo_mut(&mut self) -> &mut u32 {
+                    &mut self.0
+                }
+                const BAR_INDEX: usize = 1;
+                fn bar(&self) -> &T {
+                    &self.1
+                }
+                fn bar_mut(&mut self) -> &mut T {
+                    &mut self.1
+                }
+            }
+            impl<T> OnlyBar<T>
+            where
+                T: Clone,
+            {
+                const BAR_INDEX: usize = 0;
+                fn bar(&self) -> &T {
+                    &self.0
+                }
+                fn bar_mut(&mut self) -> &mut T {
+                    &mut self.0
+                }
+            }
+            impl<T> ::core::convert::From<Tupled<T>> for Named<T>
+            where
+                T: Clone,
+            {
+                fn from(value: Tupled<T>) -> Self {
+                    Named {
+                        foo: value.0,
+                        bar: value.1,
+                    }
+                }
+            }
+            impl<T> ::core::convert::From<Named<T>> for Tupled<T>
+            where
+                T: Clone,
+            {
+                fn from(value: Named<T>) -> Self {
+                    Tupled(value.foo, value.bar)
+                }
+            }
+        }
+        "###);
+    }
+
+    #[test]
+    fn tuple_base() {
+        insta::assert_snapshot!(run_for_fixture("tuple_base"), @r###"
+        pub mod tuple_base {
+            use structout::generate;
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `0`."]
+            struct WithoutFirst<T>(String, #[allow(dead_code)] T)
+            where
+                T: Copy;
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `0`."]
+            struct OnlyLast<T>(String, #[allow(dead_code)] T)
+            where
+                T: Copy;
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Upserted fields: `1`, `3`."]
+            struct Replaced<T>(u32, &'static str, #[allow(dead_code)] T, bool)
+            where
+                T: Copy;
+            #[doc = " Generated through `structout::generate!`."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `1`."]
+            #[doc = " - Output as a struct with named fields."]
+            struct Named<T>
+            where
+                T: Copy,
+            {
+                id: u32,
+                #[allow(dead_code)]
+                value: T,
+            }
+        }
+        "###);
+    }
+
+    #[test]
+    fn enums() {
+        insta::assert_snapshot!(run_for_fixture("enums"), @r###"
+        pub mod enums {
+            use structout::generate;
+            pub enum Event<T>
+            where
+                T: Clone,
+            {
+                Connected { id: u32 },
+                Message(T),
+                Heartbeat,
+            }
+            #[doc = " Generated from [`Event`]."]
+            #[doc = ""]
+            #[doc = " - Omitted variants: `Heartbeat`."]
+            pub enum ClientEvent<T>
+            where
+                T: Clone,
+            {
+                Connected { id: u32 },
+                Message(T),
+            }
+            #[doc = " Generated from [`Event`]."]
+            #[doc = ""]
+            #[doc = " - Omitted variants: `Heartbeat`."]
+            #[doc = " - Upserted variants: `Disconnected`."]
+            pub enum ServerEvent<T>
+            where
+                T: Clone,
+            {
+                Connected { id: u32 },
+                Message(T),
+                Disconnected(u32),
+            }
+            #[doc = " Generated from [`Event`]."]
+            #[doc = ""]
+            #[doc = " - Omitted variants: `Message`."]
+            pub enum TextEvent {
+                Connected { id: u32 },
+                Heartbeat,
+            }
+            impl<T> ::core::convert::From<ClientEvent<T>> for Event<T>
+            where
+                T: Clone,
+            {
+                fn from(value: ClientEvent<T>) -> Self {
+                    match value {
+                        ClientEvent::Connected { id } => Event::Connected { id },
+                        ClientEvent::Message(__field0) => Event::Message(__field0),
+                    }
+                }
+            }
+            impl<T> ::core::convert::TryFrom<Event<T>> for ClientEvent<T>
+            where
+                T: Clone,
+            {
+                type Error = Event<T>;
+                fn try_from(value: Event<T>) -> ::core::result::Result<Self, Self::Error> {
+                    match value {
+                        Event::Connected { id } => {
+                            ::core::result::Result::Ok(ClientEvent::Connected { id })
+                        }
+                        Event::Message(__field0) => {
+                            ::core::result::Result::Ok(ClientEvent::Message(__field0))
+                        }
+                        value => ::core::result::Result::Err(value),
+                    }
+                }
+            }
+            impl<T> ::core::convert::From<TextEvent> for Event<T>
+            where
+                T: Clone,
+            {
+                fn from(value: TextEvent) -> Self {
+                    match value {
+                        TextEvent::Connected { id } => Event::Connected { id },
+                        TextEvent::Heartbeat => Event::Heartbeat,
+                    }
+                }
+            }
+            impl<T> ::core::convert::TryFrom<Event<T>> for TextEvent
+            where
+                T: Clone,
+            {
+                type Error = Event<T>;
+                fn try_from(value: Event<T>) -> ::core::result::Result<Self, Self::Error> {
+                    match value {
+                        Event::Connected { id } => ::core::result::Result::Ok(TextEvent::Connected { id }),
+                        Event::Heartbeat => ::core::result::Result::Ok(TextEvent::Heartbeat),
+                        value => ::core::result::Result::Err(value),
+                    }
+                }
+            }
+        }
+        "###);
+    }
+
+    #[test]
+    fn field_enum() {
+        insta::assert_snapshot!(run_for_fixture("field_enum"), @r###"
+        pub mod field_enum {
+            use structout::generate;
+            pub struct Human<T>
+            where
+                T: Clone,
+            {
+                name: String,
+                age: u32,
+                extra: T,
+            }
+            #[doc = " Generated from [`Human`]."]
+            #[doc = ""]
+            #[doc = " - Output as an enum with one variant per field."]
+            pub enum HumanField<T>
+            where
+                T: Clone,
+            {
+                Name(String),
+                Age(u32),
+                Extra(T),
+            }
+            #[doc = " Generated from [`Human`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `extra`."]
+            #[doc = " - Output as an enum with one variant per field."]
+            pub enum HumanAttribute {
+                Name(String),
+                Age(u32),
+            }
+            #[doc = " Generated from [`Human`]."]
+            #[doc = ""]
+            #[doc = " - Upserted fields: `age`."]
+            #[doc = " - Output as an enum with one variant per field."]
+            pub enum HumanUpdate<T>
+            where
+                T: Clone,
+            {
+                Name(String),
+                Extra(T),
+                Age(u64),
+            }
+            impl<T> HumanField<T>
+            where
+                T: Clone,
+            {
+                pub fn apply(self, target: &mut Human<T>)
+                where
+                    T: Clone,
+                {
+                    match self {
+                        HumanField::Name(value) => target.name = value,
+                        HumanField::Age(value) => target.age = value,
+                        HumanField::Extra(value) => target.extra = value,
+                    }
+                }
+                pub fn into_field_updates(base: Human<T>) -> ::std::vec::IntoIter<Self>
+                where
+                    T: Clone,
+                {
+                    ::std::vec::Vec::from([
+                        HumanField::Name(base.name),
+                        HumanField::Age(base.age),
+                        HumanField::Extra(base.extra),
+                    ])
+                    .into_iter()
+                }
+            }
+            impl HumanAttribute {
+                pub fn apply<T>(self, target: &mut Human<T>)
+                where
+                    T: Clone,
+                {
+                    match self {
+                        HumanAttribute::Name(value) => target.name = value,
+                        HumanAttribute::Age(value) => target.age = value,
+                    }
+                }
+                pub fn into_field_updates<T>(base: Human<T>) -> ::std::vec::IntoIter<Self>
+                where
+                    T: Clone,
+                {
+                    ::std::vec::Vec::from([
+                        HumanAttribute::Name(base.name),
+                        HumanAttribute::Age(base.age),
+                    ])
+                    .into_iter()
+                }
+            }
+        }
+        "###);
+    }
+
+    #[test]
+    fn field_name_enum() {
+        insta::assert_snapshot!(run_for_fixture("field_name_enum"), @r###"
+        pub mod field_name_enum {
+            use structout::generate;
+            pub struct Account<T>
+            where
+                T: Clone,
+            {
+                id: u64,
+                r#type: String,
+                owner: T,
+            }
+            #[automatically_derived]
+            impl<T: ::core::fmt::Debug> ::core::fmt::Debug for Account<T>
+            where
+                T: Clone,
+            {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field3_finish(
+                        f,
+                        "Account",
+                        "id",
+                        &self.id,
+                        "type",
+                        &self.r#type,
+                        "owner",
+                        &&self.owner,
+                    )
+                }
+            }
+            #[automatically_derived]
+            impl<T: ::core::clone::Clone> ::core::clone::Clone for Account<T>
+            where
+                T: Clone,
+            {
+                #[inline]
+                fn clone(&self) -> Account<T> {
+                    Account {
+                        id: ::core::clone::Clone::clone(&self.id),
+                        r#type: ::core::clone::Clone::clone(&self.r#type),
+                        owner: ::core::clone::Clone::clone(&self.owner),
+                    }
+                }
+            }
+            #[automatically_derived]
+            impl<T> ::core::marker::StructuralPartialEq for Account<T> where T: Clone {}
+            #[automatically_derived]
+            impl<T: ::core::cmp::PartialEq> ::core::cmp::PartialEq for Account<T>
+            where
+                T: Clone,
+            {
+                #[inline]
+                fn eq(&self, other: &Account<T>) -> bool {
+                    self.id == other.id && self.r#type == other.r#type && self.owner == other.owner
+                }
+            }
+            #[doc = " Generated from [`Account`]."]
+            #[doc = ""]
+            #[doc = " - Output as a fieldless enum with one variant per field."]
+            pub enum AccountField {
+                Id,
+                Type,
+                Owner,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for AccountField {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::write_str(
+                        f,
+                        match self {
+                            AccountField::Id => "Id",
+                            AccountField::Type => "Type",
+                            AccountField::Owner => "Owner",
+                        },
+                    )
+                }
+            }
+            #[automatically_derived]
+            impl ::core::clone::Clone for AccountField {
+                #[inline]
+                fn clone(&self) -> AccountField {
+                    match self {
+                        AccountField::Id => AccountField::Id,
+                        AccountField::Type => AccountField::Type,
+                        AccountField::Owner => AccountField::Owner,
+                    }
+                }
+            }
+            #[automatically_derived]
+            impl ::core::marker::StructuralPartialEq for AccountField {}
+            #[automatically_derived]
+            impl ::core::cmp::PartialEq for AccountField {
+                #[inline]
+                fn eq(&self, other: &AccountField) -> bool {
+                    let __self_discr = ::core::intrinsics::discriminant_value(self);
+                    let __arg1_discr = ::core::intrinsics::discriminant_value(other);
+                    __self_discr == __arg1_discr
+                }
+            }
+            #[doc = " Generated from [`Account`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `id`."]
+            pub struct NewAccount<T>
+            where
+                T: Clone,
+            {
+                r#type: String,
+                owner: T,
+            }
+            #[automatically_derived]
+            impl<T: ::core::fmt::Debug> ::core::fmt::Debug for NewAccount<T>
+            where
+                T: Clone,
+            {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field2_finish(
+                        f,
+                        "NewAccount",
+                        "type",
+                        &self.r#type,
+                        "owner",
+                        &&self.owner,
+                    )
+                }
+            }
+            #[automatically_derived]
+            impl<T: ::core::clone::Clone> ::core::clone::Clone for NewAccount<T>
+            where
+                T: Clone,
+            {
+                #[inline]
+                fn clone(&self) -> NewAccount<T> {
+                    NewAccount {
+                        r#type: ::core::clone::Clone::clone(&self.r#type),
+                        owner: ::core::clone::Clone::clone(&self.owner),
+                    }
+                }
+            }
+            #[automatically_derived]
+            impl<T> ::core::marker::StructuralPartialEq for NewAccount<T> where T: Clone {}
+            #[automatically_derived]
+            impl<T: ::core::cmp::PartialEq> ::core::cmp::PartialEq for NewAccount<T>
+            where
+                T: Clone,
+            {
+                #[inline]
+                fn eq(&self, other: &NewAccount<T>) -> bool {
+                    self.r#type == other.r#type && self.owner == other.owner
+                }
+            }
+            #[doc = " Generated from [`Account`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `owner`."]
+            #[doc = " - Output as a tuple struct."]
+            pub struct AccountRow(u64, String);
+            #[automatically_derived]
+            impl ::core::fmt::Debug for AccountRow {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_tuple_field2_finish(f, "AccountRow", &self.0, &&self.1)
+                }
+            }
+            #[automatically_derived]
+            impl ::core::clone::Clone for AccountRow {
+                #[inline]
+                fn clone(&self) -> AccountRow {
+                    AccountRow(
+                        ::core::clone::Clone::clone(&self.0),
+                        ::core::clone::Clone::clone(&self.1),
+                    )
+                }
+            }
+            #[automatically_derived]
+            impl ::core::marker::StructuralPartialEq for AccountRow {}
+            #[automatically_derived]
+            impl ::core::cmp::PartialEq for AccountRow {
+                #[inline]
+                fn eq(&self, other: &AccountRow) -> bool {
+                    self.0 == other.0 && self.1 == other.1
+                }
+            }
+            impl AccountRow {
+                const ID_INDEX: usize = 0;
+                fn id(&self) -> &u64 {
+                    &self.0
+                }
+                fn id_mut(&mut self) -> &mut u64 {
+                    &mut self.0
+                }
+                const TYPE_INDEX: usize = 1;
+                fn r#type(&self) -> &String {
+                    &self.1
+                }
+                fn type_mut(&mut self) -> &mut String {
+                    &mut self.1
+                }
+            }
+            impl AccountField {
+                pub const ALL: &'static [AccountField] =
+                    &[AccountField::Id, AccountField::Type, AccountField::Owner];
+                pub fn as_str(&self) -> &'static str {
+                    match *self {
+                        AccountField::Id => "id",
+                        AccountField::Type => "type",
+                        AccountField::Owner => "owner",
+                    }
+                }
+                pub const fn variants(&self) -> &'static [&'static str] {
+                    match *self {
+                        AccountField::Id => &["Account", "AccountRow"],
+                        AccountField::Type => &["Account", "NewAccount", "AccountRow"],
+                        AccountField::Owner => &["Account", "NewAccount"],
+                    }
+                }
+            }
+            impl ::core::fmt::Display for AccountField {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    f.write_str(self.as_str())
+                }
+            }
+            impl ::core::str::FromStr for AccountField {
+                type Err = ::std::string::String;
+                fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                    match s {
+                        "id" => ::core::result::Result::Ok(AccountField::Id),
+                        "type" => ::core::result::Result::Ok(AccountField::Type),
+                        "owner" => ::core::result::Result::Ok(AccountField::Owner),
+                        _ => ::core::result::Result::Err(["unknown field `", s, "`"].concat()),
+                    }
+                }
+            }
+        }
+        "###);
+    }
+
+    #[test]
+    fn field_info() {
+        insta::assert_snapshot!(run_for_fixture("field_info"), @r###"
+        pub mod field_info {
+            use structout::generate;
+            pub struct Human<T>
+            where
+                T: Clone,
+            {
+                #[allow(dead_code)]
+                name: String,
+                age: u32,
+                extra: Vec<T>,
+            }
+            #[automatically_derived]
+            impl<T: ::core::fmt::Debug> ::core::fmt::Debug for Human<T>
+            where
+                T: Clone,
+            {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field3_finish(
+                        f,
+                        "Human",
+                        "name",
+                        &self.name,
+                        "age",
+                        &self.age,
+                        "extra",
+                        &&self.extra,
+                    )
+                }
+            }
+            #[doc = " Generated from [`Human`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `extra`."]
+            pub struct HumanInfo {
+                #[allow(dead_code)]
+                name: String,
+                age: u32,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for HumanInfo {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field2_finish(
+                        f,
+                        "HumanInfo",
+                        "name",
+                        &self.name,
+                        "age",
+                        &&self.age,
+                    )
+                }
+            }
+            #[doc = " Generated from [`Human`]."]
+            #[doc = ""]
+            #[doc = " - Output as a tuple struct."]
+            pub struct HumanRow<T>(String, u32, Vec<T>)
+            where
+                T: Clone;
+            #[automatically_derived]
+            impl<T: ::core::fmt::Debug> ::core::fmt::Debug for HumanRow<T>
+            where
+                T: Clone,
+            {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_tuple_field3_finish(
+                        f, "HumanRow", &self.0, &self.1, &&self.2,
+                    )
+                }
+            }
+            #[doc = " Generated from [`Human`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `age`, `extra`."]
+            pub struct HumanId {
+                #[allow(dead_code)]
+                name: String,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for HumanId {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field1_finish(f, "HumanId", "name", &&self.name)
+                }
+            }
+            impl<T> HumanRow<T>
+            where
+                T: Clone,
+            {
+                const NAME_INDEX: usize = 0;
+                fn name(&self) -> &String {
+                    &self.0
+                }
+                fn name_mut(&mut self) -> &mut String {
+                    &mut self.0
+                }
+                const AGE_INDEX: usize = 1;
+                fn age(&self) -> &u32 {
+                    &self.1
+                }
+                fn age_mut(&mut self) -> &mut u32 {
+                    &mut self.1
+                }
+                const EXTRA_INDEX: usize = 2;
+                fn extra(&self) -> &Vec<T> {
+                    &self.2
+                }
+                fn extra_mut(&mut self) -> &mut Vec<T> {
+                    &mut self.2
+                }
+            }
+            impl<T> ::core::convert::From<HumanRow<T>> for Human<T>
+            where
+                T: Clone,
+            {
+                fn from(value: HumanRow<T>) -> Self {
+                    Human {
+                        name: value.0,
+                        age: value.1,
+                        extra: value.2,
+                    }
+                }
+            }
+            impl<T> ::core::convert::From<Human<T>> for HumanRow<T>
+            where
+                T: Clone,
+            {
+                fn from(value: Human<T>) -> Self {
+                    HumanRow(value.name, value.age, value.extra)
+                }
+            }
+            impl HumanInfo {
+                pub const FIELD_NAMES: &'static [&'static str] = &["name", "age"];
+                pub const FIELD_COUNT: usize = 2;
+                pub const FIELDS: &'static [::structout_runtime::FieldInfo] = &[
+                    ::structout_runtime::FieldInfo {
+                        name: "name",
+                        ty: "String",
+                        attrs: &["#[allow(dead_code)]"],
+                    },
+                    ::structout_runtime::FieldInfo {
+                        name: "age",
+                        ty: "u32",
+                        attrs: &[],
+                    },
+                ];
+            }
+            impl<T> HumanRow<T>
+            where
+                T: Clone,
+            {
+                pub const FIELD_NAMES: &'static [&'static str] = &["name", "age", "extra"];
+                pub const FIELD_COUNT: usize = 3;
+                pub const FIELDS: &'static [::structout_runtime::FieldInfo] = &[
+                    ::structout_runtime::FieldInfo {
+                        name: "name",
+                        ty: "String",
+                        attrs: &[],
+                    },
+                    ::structout_runtime::FieldInfo {
+                        name: "age",
+                        ty: "u32",
+                        attrs: &[],
+                    },
+                    ::structout_runtime::FieldInfo {
+                        name: "extra",
+                        ty: "Vec < T >",
+                        attrs: &[],
+                    },
+                ];
+            }
+        }
+        "###);
+    }
+
+    #[test]
+    fn visit_fields() {
+        insta::assert_snapshot!(run_for_fixture("visit_fields"), @r###"
+        pub mod visit_fields {
+            use structout::generate;
+            pub struct Human<T>
+            where
+                T: Clone,
+            {
+                name: String,
+                age: u32,
+                extra: Vec<T>,
+            }
+            #[automatically_derived]
+            impl<T: ::core::fmt::Debug> ::core::fmt::Debug for Human<T>
+            where
+                T: Clone,
+            {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field3_finish(
+                        f,
+                        "Human",
+                        "name",
+                        &self.name,
+                        "age",
+                        &self.age,
+                        "extra",
+                        &&self.extra,
+                    )
+                }
+            }
+            #[doc = " Generated from [`Human`]."]
+            pub struct HumanVisited<T>
+            where
+                T: Clone,
+            {
+                name: String,
+                age: u32,
+                extra: Vec<T>,
+            }
+            #[automatically_derived]
+            impl<T: ::core::fmt::Debug> ::core::fmt::Debug for HumanVisited<T>
+            where
+                T: Clone,
+            {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field3_finish(
+                        f,
+                        "HumanVisited",
+                        "name",
+                        &self.name,
+                        "age",
+                        &self.age,
+                        "extra",
+                        &&self.extra,
+                    )
+                }
+            }
+            #[doc = " Generated from [`Human`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `age`."]
+            #[doc = " - Output as a tuple struct."]
+            pub struct HumanRow<T>(String, Vec<T>)
+            where
+                T: Clone;
+            #[automatically_derived]
+            impl<T: ::core::fmt::Debug> ::core::fmt::Debug for HumanRow<T>
+            where
+                T: Clone,
+            {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_tuple_field2_finish(f, "HumanRow", &self.0, &&self.1)
+                }
+            }
+            #[doc = " Generated from [`Human`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `name`, `age`, `extra`."]
+            #[doc = " - Output as a unit struct."]
+            pub struct HumanMarker;
+            #[automatically_derived]
+            impl ::core::fmt::Debug for HumanMarker {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::write_str(f, "HumanMarker")
+                }
+            }
+            impl<T> HumanRow<T>
+            where
+                T: Clone,
+            {
+                const NAME_INDEX: usize = 0;
+                fn name(&self) -> &String {
+                    &self.0
+                }
+                fn name_mut(&mut self) -> &mut String {
+                    &mut self.0
+                }
+                const EXTRA_INDEX: usize = 1;
+                fn extra(&self) -> &Vec<T> {
+                    &self.1
+                }
+                fn extra_mut(&mut self) -> &mut Vec<T> {
+                    &mut self.1
+                }
+            }
+            impl<T> ::structout_runtime::VisitFields for HumanVisited<T>
+            where
+                T: Clone,
+                Vec<T>: ::core::fmt::Debug,
+            {
+                fn visit_fields(&self, visitor: &mut dyn ::structout_runtime::Visitor) {
+                    visitor.field("name", &self.name);
+                    visitor.field("age", &self.age);
+                    visitor.field("extra", &self.extra);
+                }
+            }
+            impl<T> ::structout_runtime::VisitFieldsMut for HumanVisited<T>
+            where
+                T: Clone,
+                Vec<T>: ::core::any::Any,
+            {
+                fn visit_fields_mut(&mut self, visitor: &mut dyn ::structout_runtime::VisitorMut) {
+                    visitor.field("name", &mut self.name);
+                    visitor.field("age", &mut self.age);
+                    visitor.field("extra", &mut self.extra);
+                }
+            }
+            impl<T> ::structout_runtime::VisitFields for HumanRow<T>
+            where
+                T: Clone,
+                Vec<T>: ::core::fmt::Debug,
+            {
+                fn visit_fields(&self, visitor: &mut dyn ::structout_runtime::Visitor) {
+                    visitor.field("name", &self.0);
+                    visitor.field("extra", &self.1);
+                }
+            }
+            impl<T> ::structout_runtime::VisitFieldsMut for HumanRow<T>
+            where
+                T: Clone,
+                Vec<T>: ::core::any::Any,
+            {
+                fn visit_fields_mut(&mut self, visitor: &mut dyn ::structout_runtime::VisitorMut) {
+                    visitor.field("name", &mut self.0);
+                    visitor.field("extra", &mut self.1);
+                }
+            }
+            impl ::structout_runtime::VisitFields for HumanMarker {
+                fn visit_fields(&self, _visitor: &mut dyn ::structout_runtime::Visitor) {}
+            }
+            impl ::structout_runtime::VisitFieldsMut for HumanMarker {
+                fn visit_fields_mut(&mut self, _visitor: &mut dyn ::structout_runtime::VisitorMut) {}
+            }
+        }
+        "###);
+    }
+
+    #[test]
+    fn has_field() {
+        insta::assert_snapshot!(run_for_fixture("has_field"), @r###"
+        pub mod has_field {
+            use structout::generate;
+            pub struct Human<T>
+            where
+                T: Clone,
+            {
+                name: String,
+                age: u32,
+                extra: T,
+            }
+            #[doc = " Generated from [`Human`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `age`, `extra`."]
+            pub struct HumanName {
+                name: String,
+            }
+            #[doc = " Generated from [`Human`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `extra`."]
+            #[doc = " - Output as a tuple struct."]
+            pub struct HumanRow(String, u32);
+            impl HumanRow {
+                const NAME_INDEX: usize = 0;
+                fn name(&self) -> &String {
+                    &self.0
+                }
+                fn name_mut(&mut self) -> &mut String {
+                    &mut self.0
+                }
+                const AGE_INDEX: usize = 1;
+                fn age(&self) -> &u32 {
+                    &self.1
+                }
+                fn age_mut(&mut self) -> &mut u32 {
+                    &mut self.1
+                }
+            }
+            pub mod fields {
+                #[allow(non_camel_case_types)]
+                pub struct name;
+                #[allow(non_camel_case_types)]
+                pub struct age;
+                #[allow(non_camel_case_types)]
+                pub struct extra;
+            }
+            impl<T> ::structout_runtime::HasField<fields::name> for Human<T>
+            where
+                T: Clone,
+            {
+                type Type = String;
+                fn get(&self) -> &Self::Type {
+                    &self.name
+                }
+                fn get_mut(&mut self) -> &mut Self::Type {
+                    &mut self.name
+                }
+            }
+            impl<T> ::structout_runtime::HasField<fields::age> for Human<T>
+            where
+                T: Clone,
+            {
+                type Type = u32;
+                fn get(&self) -> &Self::Type {
+                    &self.age
+                }
+                fn get_mut(&mut self) -> &mut Self::Type {
+                    &mut self.age
+                }
+            }
+            impl<T> ::structout_runtime::HasField<fields::extra> for Human<T>
+            where
+                T: Clone,
+            {
+                type Type = T;
+                fn get(&self) -> &Self::Type {
+                    &self.extra
+                }
+                fn get_mut(&mut self) -> &mut Self::Type {
+                    &mut self.extra
+                }
+            }
+            impl ::structout_runtime::HasField<fields::name> for HumanName {
+                type Type = String;
+                fn get(&self) -> &Self::Type {
+                    &self.name
+                }
+                fn get_mut(&mut self) -> &mut Self::Type {
+                    &mut self.name
+                }
+            }
+            impl ::structout_runtime::HasField<fields::name> for HumanRow {
+                type Type = String;
+                fn get(&self) -> &Self::Type {
+                    &self.0
+                }
+                fn get_mut(&mut self) -> &mut Self::Type {
+                    &mut self.0
+                }
+            }
+            impl ::structout_runtime::HasField<fields::age> for HumanRow {
+                type Type = u32;
+                fn get(&self) -> &Self::Type {
+                    &self.1
+                }
+                fn get_mut(&mut self) -> &mut Self::Type {
+                    &mut self.1
+                }
+            }
+            pub fn greet<T: structout_runtime::HasField<fields::name, Type = String>>(human: &T) -> String {
+                ::alloc::__export::must_use({
+                    ::alloc::fmt::format(format_args!("Hello, {0}", human.get()))
+                })
+            }
+        }
+        "###);
+    }
+
+    #[test]
+    fn common_trait() {
+        insta::assert_snapshot!(run_for_fixture("common_trait"), @r###"
+        pub mod common_trait {
+            use structout::generate;
+            pub struct Human<T>
+            where
+                T: Clone,
+            {
+                name: String,
+                age: u32,
+                extra: T,
+            }
+            #[doc = " Generated from [`Human`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `age`."]
+            pub struct Child<T>
+            where
+                T: Clone,
+            {
+                name: String,
+                extra: T,
+            }
+            #[doc = " Generated from [`Human`]."]
+            #[doc = ""]
+            #[doc = " - Upserted fields: `age`."]
+            pub struct Adult<T>
+            where
+                T: Clone,
+            {
+                name: String,
+                extra: T,
+                age: u64,
+            }
+            #[doc = " Generated from [`Human`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `name`."]
+            #[doc = " - Output as a tuple struct."]
+            pub struct Record<T>(u32, T)
+            where
+                T: Clone;
+            impl<T> Record<T>
+            where
+                T: Clone,
+            {
+                const AGE_INDEX: usize = 0;
+                fn age(&self) -> &u32 {
+                    &self.0
+                }
+                fn age_mut(&mut self) -> &mut u32 {
+                    &mut self.0
+                }
+                const EXTRA_INDEX: usize = 1;
+                fn extra(&self) -> &T {
+                    &self.1
+                }
+                fn extra_mut(&mut self) -> &mut T {
+                    &mut self.1
+                }
+            }
+            pub trait HumanLike<T> {
+                fn name(&self) -> &String;
+                fn set_name(&mut self, value: String);
+                fn extra(&self) -> &T;
+                fn set_extra(&mut self, value: T);
+            }
+            impl<T> HumanLike<T> for Child<T>
+            where
+                T: Clone,
+            {
+                fn name(&self) -> &String {
+                    &self.name
+                }
+                fn set_name(&mut self, value: String) {
+                    self.name = value;
+                }
+                fn extra(&self) -> &T {
+                    &self.extra
+                }
+                fn set_extra(&mut self, value: T) {
+                    self.extra = value;
+                }
+            }
+            impl<T> HumanLike<T> for Adult<T>
+            where
+                T: Clone,
+            {
+                fn name(&self) -> &String {
+                    &self.name
+                }
+                fn set_name(&mut self, value: String) {
+                    self.name = value;
+                }
+                fn extra(&self) -> &T {
+                    &self.extra
+                }
+                fn set_extra(&mut self, value: T) {
+                    self.extra = value;
+                }
+            }
+            pub trait Aged<T> {
+                fn age(&self) -> &u32;
+                fn extra(&self) -> &T;
+            }
+            impl<T> Aged<T> for Record<T>
+            where
+                T: Clone,
+            {
+                fn age(&self) -> &u32 {
+                    &self.0
+                }
+                fn extra(&self) -> &T {
+                    &self.1
+                }
+            }
+        }
+        "###);
+    }
+
+    #[test]
+    fn any() {
+        insta::assert_snapshot!(run_for_fixture("any"), @r###"
+        pub mod any {
+            use structout::generate;
+            pub struct Human<T>
+            where
+                T: Clone,
+            {
+                name: String,
+                surname: String,
+                extra: T,
+            }
+            #[automatically_derived]
+            impl<T: ::core::fmt::Debug> ::core::fmt::Debug for Human<T>
+            where
+                T: Clone,
+            {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field3_finish(
+                        f,
+                        "Human",
+                        "name",
+                        &self.name,
+                        "surname",
+                        &self.surname,
+                        "extra",
+                        &&self.extra,
+                    )
+                }
+            }
+            #[doc = " Generated from [`Human`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `extra`."]
+            pub struct HumanEditableParts {
+                name: String,
+                surname: String,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for HumanEditableParts {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field2_finish(
+                        f,
+                        "HumanEditableParts",
+                        "name",
+                        &self.name,
+                        "surname",
+                        &&self.surname,
+                    )
+                }
+            }
+            #[doc = " Generated from [`Human`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `surname`."]
+            #[doc = " - Output as a tuple struct."]
+            pub struct HumanRow<T>(String, T)
+            where
+                T: Clone;
+            #[automatically_derived]
+            impl<T: ::core::fmt::Debug> ::core::fmt::Debug for HumanRow<T>
+            where
+                T: Clone,
+            {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_tuple_field2_finish(f, "HumanRow", &self.0, &&self.1)
+                }
+            }
+            #[doc = " Generated from [`Human`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `extra`."]
+            #[doc = " - Upserted fields: `surname`."]
+            pub struct HumanNickname {
+                name: String,
+                surname: Option<String>,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for HumanNickname {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field2_finish(
+                        f,
+                        "HumanNickname",
+                        "name",
+                        &self.name,
+                        "surname",
+                        &&self.surname,
+                    )
+                }
+            }
+            impl<T> HumanRow<T>
+            where
+                T: Clone,
+            {
+                const NAME_INDEX: usize = 0;
+                fn name(&self) -> &String {
+                    &self.0
+                }
+                fn name_mut(&mut self) -> &mut String {
+                    &mut self.0
+                }
+                const EXTRA_INDEX: usize = 1;
+                fn extra(&self) -> &T {
+                    &self.1
+                }
+                fn extra_mut(&mut self) -> &mut T {
+                    &mut self.1
+                }
+            }
+            pub enum AnyHuman<T>
+            where
+                T: Clone,
+            {
+                Human(Human<T>),
+                HumanEditableParts(HumanEditableParts),
+                HumanRow(HumanRow<T>),
+                HumanNickname(HumanNickname),
+            }
+            impl<T> ::core::convert::From<Human<T>> for AnyHuman<T>
+            where
+                T: Clone,
+            {
+                fn from(value: Human<T>) -> Self {
+                    AnyHuman::Human(value)
+                }
+            }
+            impl<T> ::core::convert::From<HumanEditableParts> for AnyHuman<T>
+            where
+                T: Clone,
+            {
+                fn from(value: HumanEditableParts) -> Self {
+                    AnyHuman::HumanEditableParts(value)
+                }
+            }
+            impl<T> ::core::convert::From<HumanRow<T>> for AnyHuman<T>
+            where
+                T: Clone,
+            {
+                fn from(value: HumanRow<T>) -> Self {
+                    AnyHuman::HumanRow(value)
+                }
+            }
+            impl<T> ::core::convert::From<HumanNickname> for AnyHuman<T>
+            where
+                T: Clone,
+            {
+                fn from(value: HumanNickname) -> Self {
+                    AnyHuman::HumanNickname(value)
+                }
+            }
+            impl<T> AnyHuman<T>
+            where
+                T: Clone,
+            {
+                pub fn name(&self) -> &String {
+                    match *self {
+                        AnyHuman::Human(ref value) => &value.name,
+                        AnyHuman::HumanEditableParts(ref value) => &value.name,
+                        AnyHuman::HumanRow(ref value) => &value.0,
+                        AnyHuman::HumanNickname(ref value) => &value.name,
+                    }
+                }
+                pub fn extra(&self) -> ::core::option::Option<&T> {
+                    match *self {
+                        AnyHuman::Human(ref value) => ::core::option::Option::Some(&value.extra),
+                        AnyHuman::HumanRow(ref value) => ::core::option::Option::Some(&value.1),
+                        _ => ::core::option::Option::None,
+                    }
+                }
+            }
+        }
+        "###);
+    }
+
+    #[test]
+    fn impl_templates() {
+        insta::assert_snapshot!(run_for_fixture("impl_templates"), @r###"
+        pub mod impl_templates {
+            use structout::generate;
+            pub struct Human<T>
+            where
+                T: Clone,
+            {
+                name: String,
+                surname: String,
+                extra: T,
+            }
+            #[doc = " Generated from [`Human`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `extra`."]
+            pub struct HumanName {
+                name: String,
+                surname: String,
+            }
+            #[doc = " Generated from [`Human`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `name`, `surname`."]
+            pub struct HumanExtra<T>
+            where
+                T: Clone,
+            {
+                extra: T,
+            }
+            #[doc = " Generated from [`Human`]."]
+            #[doc = ""]
+            #[doc = " - Output as a tuple struct."]
+            pub struct HumanRow<T>(String, String, T)
+            where
+                T: Clone;
+            impl<T> HumanRow<T>
+            where
+                T: Clone,
+            {
+                const NAME_INDEX: usize = 0;
+                fn name(&self) -> &String {
+                    &self.0
+                }
+                fn name_mut(&mut self) -> &mut String {
+                    &mut self.0
+                }
+                const SURNAME_INDEX: usize = 1;
+                fn surname(&self) -> &String {
+                    &self.1
+                }
+                fn surname_mut(&mut self) -> &mut String {
+                    &mut self.1
+                }
+                const EXTRA_INDEX: usize = 2;
+                fn extra(&self) -> &T {
+                    &self.2
+                }
+                fn extra_mut(&mut self) -> &mut T {
+                    &mut self.2
+                }
+            }
+            impl<T> ::core::convert::From<HumanRow<T>> for Human<T>
+            where
+                T: Clone,
+            {
+                fn from(value: HumanRow<T>) -> Self {
+                    Human {
+                        name: value.0,
+                        surname: value.1,
+                        extra: value.2,
+                    }
+                }
+            }
+            impl<T> ::core::convert::From<Human<T>> for HumanRow<T>
+            where
+                T: Clone,
+            {
+                fn from(value: Human<T>) -> Self {
+                    HumanRow(value.name, value.surname, value.extra)
+                }
+            }
+            impl<T> Human<T>
+            where
+                T: Clone,
+            {
+                pub fn full_name(&self) -> String {
+                    [self.name.as_str(), self.surname.as_str()].join(" ")
+                }
+                pub fn extra(&self) -> &T {
+                    &self.extra
+                }
+                pub fn repeat_extra(&self, count: usize) -> Vec<T> {
+                    ::alloc::vec::from_elem(self.extra.clone(), count)
+                }
+            }
+            impl HumanName {
+                pub fn full_name(&self) -> String {
+                    [self.name.as_str(), self.surname.as_str()].join(" ")
+                }
+            }
+            impl<T> HumanExtra<T>
+            where
+                T: Clone,
+            {
+                pub fn extra(&self) -> &T {
+                    &self.extra
+                }
+                pub fn repeat_extra(&self, count: usize) -> Vec<T> {
+                    ::alloc::vec::from_elem(self.extra.clone(), count)
+                }
+            }
+            impl<T> HumanRow<T>
+            where
+                T: Clone,
+            {
+                pub fn first(&self) -> &String {
+                    &self.0
+                }
+            }
+        }
+        "###);
+    }
+
+    #[test]
+    fn attr_if() {
+        insta::assert_snapshot!(run_for_fixture("attr_if"), @r###"
+        pub mod attr_if {
+            use structout::generate;
+            pub struct Record {
+                id: u64,
+                name: String,
+                deleted: bool,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for Record {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field3_finish(
+                        f,
+                        "Record",
+                        "id",
+                        &self.id,
+                        "name",
+                        &self.name,
+                        "deleted",
+                        &&self.deleted,
+                    )
+                }
+            }
+            #[automatically_derived]
+            impl ::core::clone::Clone for Record {
+                #[inline]
+                fn clone(&self) -> Record {
+                    Record {
+                        id: ::core::clone::Clone::clone(&self.id),
+                        name: ::core::clone::Clone::clone(&self.name),
+                        deleted: ::core::clone::Clone::clone(&self.deleted),
+                    }
+                }
+            }
+            #[automatically_derived]
+            impl ::core::marker::StructuralPartialEq for Record {}
+            #[automatically_derived]
+            impl ::core::cmp::PartialEq for Record {
+                #[inline]
+                fn eq(&self, other: &Record) -> bool {
+                    self.id == other.id && self.deleted == other.deleted && self.name == other.name
+                }
+            }
+            #[doc = " Generated from [`Record`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `name`, `deleted`."]
+            pub struct RecordKey {
+                #[doc = "The key of the record"]
+                id: u64,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for RecordKey {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field1_finish(f, "RecordKey", "id", &&self.id)
+                }
+            }
+            #[automatically_derived]
+            impl ::core::clone::Clone for RecordKey {
+                #[inline]
+                fn clone(&self) -> RecordKey {
+                    RecordKey {
+                        id: ::core::clone::Clone::clone(&self.id),
+                    }
+                }
+            }
+            #[automatically_derived]
+            impl ::core::marker::StructuralPartialEq for RecordKey {}
+            #[automatically_derived]
+            impl ::core::cmp::PartialEq for RecordKey {
+                #[inline]
+                fn eq(&self, other: &RecordKey) -> bool {
+                    self.id == other.id
+                }
+            }
+            #[automatically_derived]
+            impl ::core::hash::Hash for RecordKey {
+                #[inline]
+                fn hash<__H: ::core::hash::Hasher>(&self, state: &mut __H) {
+                    ::core::hash::Hash::hash(&self.id, state)
+                }
+            }
+            #[automatically_derived]
+            impl ::core::cmp::Eq for RecordKey {
+                #[doc(hidden)]
+                #[coverage(off)]
+                fn assert_fields_are_eq(&self) {
+                    let _: ::core::cmp::AssertParamIsEq<u64>;
+                }
+            }
+            #[doc = " Generated from [`Record`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `id`, `deleted`."]
+            pub struct RecordName {
+                name: String,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for RecordName {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field1_finish(f, "RecordName", "name", &&self.name)
+                }
+            }
+            #[automatically_derived]
+            impl ::core::clone::Clone for RecordName {
+                #[inline]
+                fn clone(&self) -> RecordName {
+                    RecordName {
+                        name: ::core::clone::Clone::clone(&self.name),
+                    }
+                }
+            }
+            #[automatically_derived]
+            impl ::core::marker::StructuralPartialEq for RecordName {}
+            #[automatically_derived]
+            impl ::core::cmp::PartialEq for RecordName {
+                #[inline]
+                fn eq(&self, other: &RecordName) -> bool {
+                    self.name == other.name
+                }
+            }
+            #[automatically_derived]
+            impl ::core::cmp::PartialOrd for RecordName {
+                #[inline]
+                fn partial_cmp(&self, other: &RecordName) -> ::core::option::Option<::core::cmp::Ordering> {
+                    ::core::cmp::PartialOrd::partial_cmp(&self.name, &other.name)
+                }
+            }
+            #[doc = " Generated from [`Record`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `name`."]
+            pub struct DeletedRecord {
+                id: u64,
+                #[doc = "Always true"]
+                deleted: bool,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for DeletedRecord {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field2_finish(
+                        f,
+                        "DeletedRecord",
+                        "id",
+                        &self.id,
+                        "deleted",
+                        &&self.deleted,
+                    )
+                }
+            }
+            #[automatically_derived]
+            impl ::core::clone::Clone for DeletedRecord {
+                #[inline]
+                fn clone(&self) -> DeletedRecord {
+                    DeletedRecord {
+                        id: ::core::clone::Clone::clone(&self.id),
+                        deleted: ::core::clone::Clone::clone(&self.deleted),
+                    }
+                }
+            }
+            #[automatically_derived]
+            impl ::core::marker::StructuralPartialEq for DeletedRecord {}
+            #[automatically_derived]
+            impl ::core::cmp::PartialEq for DeletedRecord {
+                #[inline]
+                fn eq(&self, other: &DeletedRecord) -> bool {
+                    self.id == other.id && self.deleted == other.deleted
+                }
+            }
+            pub struct Pair(u32, String, bool);
+            #[doc = " Generated from [`Pair`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `0`."]
+            pub struct PairTail(String, #[doc = "The flag"] bool);
+            #[automatically_derived]
+            impl ::core::fmt::Debug for PairTail {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_tuple_field2_finish(f, "PairTail", &self.0, &&self.1)
+                }
+            }
+        }
+        "###);
+    }
+
+    #[test]
+    fn field_attrs() {
+        insta::assert_snapshot!(run_for_fixture("field_attrs"), @r###"
+        pub mod field_attrs {
+            use structout::generate;
+            pub struct User {
+                #[doc = "The id of the user"]
+                id: u64,
+                #[allow(dead_code)]
+                #[doc = "The name of the user"]
+                name: String,
+            }
+            #[doc = " Generated from [`User`]."]
+            pub struct UserDto {
+                #[doc = "The id of the user"]
+                #[allow(clippy::all)]
+                id: u64,
+                #[allow(dead_code)]
+                #[doc = "The name of the user"]
+                #[allow(unused)]
+                #[allow(clippy::all)]
+                name: String,
+            }
+            #[doc = " Generated from [`User`]."]
+            pub struct UserRow {
+                id: u64,
+                name: String,
+            }
+            #[doc = " Generated from [`User`]."]
+            pub struct UserPatch {
+                #[doc = "The id of the user to patch"]
+                id: u64,
+                #[allow(dead_code)]
+                name: String,
+            }
+        }
+        "###);
+    }
+
+    #[test]
+    fn strip_attrs() {
+        insta::assert_snapshot!(run_for_fixture("strip_attrs"), @r###"
+        pub mod strip_attrs {
+            use structout::generate;
+            #[allow(dead_code, missing_docs)]
+            #[doc = "A user"]
+            pub struct User {
+                id: u64,
+                name: String,
+            }
+            #[automatically_derived]
+            #[allow(dead_code, missing_docs)]
+            impl ::core::fmt::Debug for User {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field2_finish(
+                        f,
+                        "User",
+                        "id",
+                        &self.id,
+                        "name",
+                        &&self.name,
+                    )
+                }
+            }
+            #[automatically_derived]
+            #[allow(dead_code, missing_docs)]
+            impl ::core::default::Default for User {
+                #[inline]
+                fn default() -> User {
+                    User {
+                        id: ::core::default::Default::default(),
+                        name: ::core::default::Default::default(),
+                    }
+                }
+            }
+            #[automatically_derived]
+            #[allow(dead_code, missing_docs)]
+            impl ::core::clone::Clone for User {
+                #[inline]
+                fn clone(&self) -> User {
+                    User {
+                        id: ::core::clone::Clone::clone(&self.id),
+                        name: ::core::clone::Clone::clone(&self.name),
+                    }
+                }
+            }
+            #[allow(dead_code, missing_docs)]
+            #[doc = "A user"]
+            #[doc = ""]
+            #[doc = " Generated from [`User`]."]
+            #[doc = ""]
+            #[doc = " - Upserted fields: `name`."]
+            pub struct UserRef {
+                id: u64,
+                name: &'static std::path::Path,
+            }
+            #[automatically_derived]
+            #[allow(dead_code, missing_docs)]
+            impl ::core::fmt::Debug for UserRef {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field2_finish(
+                        f,
+                        "UserRef",
+                        "id",
+                        &self.id,
+                        "name",
+                        &&self.name,
+                    )
+                }
+            }
+            #[automatically_derived]
+            #[allow(dead_code, missing_docs)]
+            impl ::core::clone::Clone for UserRef {
+                #[inline]
+                fn clone(&self) -> UserRef {
+                    UserRef {
+                        id: ::core::clone::Clone::clone(&self.id),
+                        name: ::core::clone::Clone::clone(&self.name),
+                    }
+                }
+            }
+            #[allow(dead_code)]
+            #[doc = " Generated from [`User`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `name`."]
+            pub struct UserId {
+                id: u64,
+            }
+            #[automatically_derived]
+            #[allow(dead_code)]
+            impl ::core::fmt::Debug for UserId {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field1_finish(f, "UserId", "id", &&self.id)
+                }
+            }
+            #[automatically_derived]
+            #[allow(dead_code)]
+            impl ::core::default::Default for UserId {
+                #[inline]
+                fn default() -> UserId {
+                    UserId {
+                        id: ::core::default::Default::default(),
+                    }
+                }
+            }
+            #[automatically_derived]
+            #[allow(dead_code)]
+            impl ::core::clone::Clone for UserId {
+                #[inline]
+                fn clone(&self) -> UserId {
+                    UserId {
+                        id: ::core::clone::Clone::clone(&self.id),
+                    }
+                }
+            }
+            #[doc = " Generated from [`User`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `id`."]
+            pub struct UserName {
+                name: String,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for UserName {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field1_finish(f, "UserName", "name", &&self.name)
+                }
+            }
+            #[allow(dead_code, missing_docs)]
+            #[doc = " Generated from [`User`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `name`."]
+            pub struct UserKey {
+                id: u64,
+            }
+        }
+        "###);
+    }
+
+    #[test]
+    fn derives() {
+        insta::assert_snapshot!(run_for_fixture("derives"), @r###"
+        pub mod derives {
+            use structout::generate;
+            pub struct Settings {
+                volume: u8,
+                theme: String,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for Settings {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field2_finish(
+                        f,
+                        "Settings",
+                        "volume",
+                        &self.volume,
+                        "theme",
+                        &&self.theme,
+                    )
+                }
             }
-            struct OnlyFoo {
-                foo: u32,
+            #[automatically_derived]
+            impl ::core::default::Default for Settings {
+                #[inline]
+                fn default() -> Settings {
+                    Settings {
+                        volume: ::core::default::Default::default(),
+                        theme: ::core::default::Default::default(),
+                    }
+                }
+            }
+            #[doc = " Generated from [`Settings`]."]
+            pub struct SettingsUpdate {
+                volume: u8,
+                theme: String,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for SettingsUpdate {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field2_finish(
+                        f,
+                        "SettingsUpdate",
+                        "volume",
+                        &self.volume,
+                        "theme",
+                        &&self.theme,
+                    )
+                }
+            }
+            #[automatically_derived]
+            impl ::core::default::Default for SettingsUpdate {
+                #[inline]
+                fn default() -> SettingsUpdate {
+                    SettingsUpdate {
+                        volume: ::core::default::Default::default(),
+                        theme: ::core::default::Default::default(),
+                    }
+                }
+            }
+            #[automatically_derived]
+            impl ::core::clone::Clone for SettingsUpdate {
+                #[inline]
+                fn clone(&self) -> SettingsUpdate {
+                    SettingsUpdate {
+                        volume: ::core::clone::Clone::clone(&self.volume),
+                        theme: ::core::clone::Clone::clone(&self.theme),
+                    }
+                }
+            }
+            #[automatically_derived]
+            impl ::core::marker::StructuralPartialEq for SettingsUpdate {}
+            #[automatically_derived]
+            impl ::core::cmp::PartialEq for SettingsUpdate {
+                #[inline]
+                fn eq(&self, other: &SettingsUpdate) -> bool {
+                    self.volume == other.volume && self.theme == other.theme
+                }
+            }
+            #[doc = " Generated from [`Settings`]."]
+            #[doc = ""]
+            #[doc = " - Upserted fields: `theme`."]
+            pub struct SettingsPath {
+                volume: u8,
+                theme: &'static std::path::Path,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for SettingsPath {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field2_finish(
+                        f,
+                        "SettingsPath",
+                        "volume",
+                        &self.volume,
+                        "theme",
+                        &&self.theme,
+                    )
+                }
+            }
+            #[doc = " Generated from [`Settings`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `theme`."]
+            pub struct SettingsVolume {
+                volume: u8,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for SettingsVolume {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field1_finish(
+                        f,
+                        "SettingsVolume",
+                        "volume",
+                        &&self.volume,
+                    )
+                }
+            }
+            #[automatically_derived]
+            impl ::core::default::Default for SettingsVolume {
+                #[inline]
+                fn default() -> SettingsVolume {
+                    SettingsVolume {
+                        volume: ::core::default::Default::default(),
+                    }
+                }
+            }
+            #[automatically_derived]
+            #[doc(hidden)]
+            unsafe impl ::core::clone::TrivialClone for SettingsVolume {}
+            #[automatically_derived]
+            impl ::core::clone::Clone for SettingsVolume {
+                #[inline]
+                fn clone(&self) -> SettingsVolume {
+                    let _: ::core::clone::AssertParamIsClone<u8>;
+                    *self
+                }
+            }
+            #[automatically_derived]
+            impl ::core::marker::Copy for SettingsVolume {}
+            #[automatically_derived]
+            impl ::core::hash::Hash for SettingsVolume {
+                #[inline]
+                fn hash<__H: ::core::hash::Hasher>(&self, state: &mut __H) {
+                    ::core::hash::Hash::hash(&self.volume, state)
+                }
             }
         }
         "###);
     }
 
     #[test]
-    fn wheres() {
-        insta::assert_snapshot!(run_for_fixture("wheres"), @r###"
-        pub mod wheres {
+    fn docs() {
+        insta::assert_snapshot!(run_for_fixture("docs"), @r###"
+        pub mod docs {
             use structout::generate;
-            struct OnlyBar<C>
-            where
-                C: Copy,
-            {
-                bar: C,
+            #[doc = " A user of the service"]
+            pub struct User {
+                id: u64,
+                name: String,
+                password_hash: String,
             }
-            struct OnlyFoo<S>
-            where
-                S: Sized,
-            {
-                foo: S,
+            #[doc = " A user of the service"]
+            #[doc = ""]
+            #[doc = " What clients get to see of a user."]
+            #[doc = ""]
+            #[doc = " Generated from [`User`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `password_hash`."]
+            pub struct UserView {
+                id: u64,
+                name: String,
+            }
+            #[doc = " A user of the service"]
+            #[doc = ""]
+            #[doc = " Generated from [`User`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `id`, `password_hash`."]
+            #[doc = " - Upserted fields: `name`, `email`."]
+            pub struct UserUpdate {
+                name: Option<String>,
+                email: Option<String>,
+            }
+            #[doc = " A user of the service"]
+            #[doc = ""]
+            #[doc = " Generated from [`User`]."]
+            #[doc = ""]
+            #[doc = " - Output as a tuple struct."]
+            pub struct UserRow(u64, String, String);
+            impl UserRow {
+                const ID_INDEX: usize = 0;
+                fn id(&self) -> &u64 {
+                    &self.0
+                }
+                fn id_mut(&mut self) -> &mut u64 {
+                    &mut self.0
+                }
+                const NAME_INDEX: usize = 1;
+                fn name(&self) -> &String {
+                    &self.1
+                }
+                fn name_mut(&mut self) -> &mut String {
+                    &mut self.1
+                }
+                const PASSWORD_HASH_INDEX: usize = 2;
+                fn password_hash(&self) -> &String {
+                    &self.2
+                }
+                fn password_hash_mut(&mut self) -> &mut String {
+                    &mut self.2
+                }
+            }
+            impl ::core::convert::From<UserRow> for User {
+                fn from(value: UserRow) -> Self {
+                    User {
+                        id: value.0,
+                        name: value.1,
+                        password_hash: value.2,
+                    }
+                }
+            }
+            impl ::core::convert::From<User> for UserRow {
+                fn from(value: User) -> Self {
+                    UserRow(value.id, value.name, value.password_hash)
+                }
+            }
+            pub struct Session {
+                token: String,
+                user_id: u64,
+            }
+            #[doc = " Only the token of a session."]
+            pub struct SessionToken {
+                token: String,
+            }
+            pub struct SessionUser {
+                user_id: u64,
             }
         }
         "###);
     }
 
     #[test]
-    fn simple() {
-        insta::assert_snapshot!(run_for_fixture("simple"), @r###"
-        pub mod simple {
+    fn field_matrix() {
+        insta::assert_snapshot!(run_for_fixture("field_matrix"), @r###"
+        pub mod field_matrix {
             use structout::generate;
-            struct WithoutFoo {
-                bar: u64,
-                baz: String,
+            #[doc = " An account of the service"]
+            #[doc = ""]
+            #[doc = " Fields of the structs generated from this definition:"]
+            #[doc = ""]
+            #[doc = " | Field | [`Account`] | [`AccountView`] | [`AccountSignup`] |"]
+            #[doc = " | --- | :---: | :---: | :---: |"]
+            #[doc = " | `id` | ✓ | ✓ |   |"]
+            #[doc = " | `email` | ✓ | ✓ | ✓ |"]
+            #[doc = " | `password_hash` | ✓ |   | ✓ |"]
+            #[doc = " | `password` |   |   | ✓ |"]
+            pub struct Account {
+                id: u64,
+                email: String,
+                password_hash: String,
             }
-            struct WithoutBar {
-                foo: u32,
-                baz: String,
+            #[doc = " An account of the service"]
+            pub struct AccountView {
+                id: u64,
+                email: String,
             }
-            # [object (context = Database)]
-            #[object(config = "latest")]
-            struct WithAttrs {
-                foo: u32,
-                bar: u64,
-                baz: String,
+            #[doc = " An account of the service"]
+            pub struct AccountSignup {
+                email: String,
+                password_hash: String,
+                password: String,
+            }
+            pub struct Token {
+                token: String,
+                expires_at: u64,
+            }
+            pub struct TokenValue(String);
+            impl TokenValue {
+                const TOKEN_INDEX: usize = 0;
+                fn token(&self) -> &String {
+                    &self.0
+                }
+                fn token_mut(&mut self) -> &mut String {
+                    &mut self.0
+                }
             }
+            #[doc = " Fields of the structs generated from this definition:"]
+            #[doc = ""]
+            #[doc = " | Field | [`Token`](super::Token) | [`TokenValue`](super::TokenValue) |"]
+            #[doc = " | --- | :---: | :---: |"]
+            #[doc = " | `token` | ✓ | ✓ |"]
+            #[doc = " | `expires_at` | ✓ |   |"]
+            pub mod token_fields {}
         }
         "###);
     }
 
     #[test]
-    fn visibility() {
-        insta::assert_snapshot!(run_for_fixture("visibility"), @r###"
-        pub mod visibility {
+    fn cfgs() {
+        insta::assert_snapshot!(run_for_fixture("cfgs"), @r###"
+        pub mod cfgs {
             use structout::generate;
-            pub(crate) struct Everything {
-                foo: u32,
+            pub struct Account {
+                id: u64,
+                #[allow(dead_code)]
+                balance: i64,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for Account {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field2_finish(
+                        f,
+                        "Account",
+                        "id",
+                        &self.id,
+                        "balance",
+                        &&self.balance,
+                    )
+                }
+            }
+            #[doc = " Generated from [`Account`]."]
+            #[doc = ""]
+            #[doc = " - Output as a tuple struct."]
+            pub struct AccountRow(u64, i64);
+            #[automatically_derived]
+            impl ::core::fmt::Debug for AccountRow {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_tuple_field2_finish(f, "AccountRow", &self.0, &&self.1)
+                }
+            }
+            #[doc = " Generated from [`Account`]."]
+            #[doc = ""]
+            #[doc = " - Output as an enum with one variant per field."]
+            pub enum AccountField {
+                Id(u64),
+                Balance(i64),
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for AccountField {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    match self {
+                        AccountField::Id(__self_0) => {
+                            ::core::fmt::Formatter::debug_tuple_field1_finish(f, "Id", &__self_0)
+                        }
+                        AccountField::Balance(__self_0) => {
+                            ::core::fmt::Formatter::debug_tuple_field1_finish(f, "Balance", &__self_0)
+                        }
+                    }
+                }
+            }
+            #[doc = " Generated from [`Account`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `balance`."]
+            pub struct AccountAudit {
+                id: u64,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for AccountAudit {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field1_finish(f, "AccountAudit", "id", &&self.id)
+                }
+            }
+            impl AccountRow {
+                const ID_INDEX: usize = 0;
+                fn id(&self) -> &u64 {
+                    &self.0
+                }
+                fn id_mut(&mut self) -> &mut u64 {
+                    &mut self.0
+                }
+                const BALANCE_INDEX: usize = 1;
+                fn balance(&self) -> &i64 {
+                    &self.1
+                }
+                fn balance_mut(&mut self) -> &mut i64 {
+                    &mut self.1
+                }
+            }
+            impl ::core::convert::From<AccountRow> for Account {
+                fn from(value: AccountRow) -> Self {
+                    Account {
+                        id: value.0,
+                        balance: value.1,
+                    }
+                }
+            }
+            impl ::core::convert::From<Account> for AccountRow {
+                fn from(value: Account) -> Self {
+                    AccountRow(value.id, value.balance)
+                }
+            }
+            impl AccountField {
+                pub fn apply(self, target: &mut Account) {
+                    match self {
+                        AccountField::Id(value) => target.id = value,
+                        AccountField::Balance(value) => target.balance = value,
+                    }
+                }
+                pub fn into_field_updates(base: Account) -> ::std::vec::IntoIter<Self> {
+                    ::std::vec::Vec::from([
+                        AccountField::Id(base.id),
+                        AccountField::Balance(base.balance),
+                    ])
+                    .into_iter()
+                }
             }
         }
         "###);
     }
 
     #[test]
-    fn include() {
-        insta::assert_snapshot!(run_for_fixture("include"), @r###"
-        pub mod include {
+    fn presets() {
+        insta::assert_snapshot!(run_for_fixture("presets"), @r###"
+        pub mod presets {
             use structout::generate;
-            struct WithoutFoo {
-                bar: u64,
+            pub struct User {
+                id: u64,
+                name: String,
             }
-            struct WithoutBar {
-                foo: u32,
+            #[automatically_derived]
+            impl ::core::fmt::Debug for User {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field2_finish(
+                        f,
+                        "User",
+                        "id",
+                        &self.id,
+                        "name",
+                        &&self.name,
+                    )
+                }
+            }
+            #[doc = " Generated from [`User`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `id`."]
+            pub struct UserCreate {
+                name: String,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for UserCreate {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field1_finish(f, "UserCreate", "name", &&self.name)
+                }
+            }
+            #[doc = " Generated from [`User`]."]
+            pub struct UserView {
+                id: u64,
+                name: String,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for UserView {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field2_finish(
+                        f,
+                        "UserView",
+                        "id",
+                        &self.id,
+                        "name",
+                        &&self.name,
+                    )
+                }
+            }
+            #[doc = " Generated from [`User`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `name`."]
+            pub struct UserAdmin {
+                id: u64,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for UserAdmin {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field1_finish(f, "UserAdmin", "id", &&self.id)
+                }
+            }
+            pub struct Post {
+                id: u64,
+                title: String,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for Post {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field2_finish(
+                        f,
+                        "Post",
+                        "id",
+                        &self.id,
+                        "title",
+                        &&self.title,
+                    )
+                }
+            }
+            #[doc = " Generated from [`Post`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `id`."]
+            pub struct PostCreate {
+                title: String,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for PostCreate {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field1_finish(
+                        f,
+                        "PostCreate",
+                        "title",
+                        &&self.title,
+                    )
+                }
+            }
+            #[doc = " Generated from [`Post`]."]
+            pub struct PostView {
+                id: u64,
+                title: String,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for PostView {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field2_finish(
+                        f,
+                        "PostView",
+                        "id",
+                        &self.id,
+                        "title",
+                        &&self.title,
+                    )
+                }
             }
         }
         "###);
     }
 
     #[test]
-    fn as_tuple() {
-        insta::assert_snapshot!(run_for_fixture("as_tuple"), @r###"
-        pub mod as_tuple {
-            use structout::generate;
-            struct OnlyBar<C>(C, i32)
-            where
-                C: Copy;
-            struct OnlyFoo<S>(S, i32)
-            where
-                S: Sized;
+    fn templates() {
+        insta::assert_snapshot!(run_for_fixture("templates"), @r###"
+        pub mod templates {
+            use structout::{generate, template};
+            #[doc = " Outputs for creating and viewing an entity"]
+            macro_rules! Crud {
+                ($ ($input : tt) *) =>
+                {
+                    :: structout :: generate!
+                    {
+                        preset Crud
+                        { "{Base}Create" => [omit(id)], "{Base}View" => [], } $
+                        ($input) *
+                    }
+                };
+            }
+            pub struct Comment {
+                id: u64,
+                text: String,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for Comment {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field2_finish(
+                        f,
+                        "Comment",
+                        "id",
+                        &self.id,
+                        "text",
+                        &&self.text,
+                    )
+                }
+            }
+            #[doc = " Generated from [`Comment`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `id`."]
+            pub struct CommentCreate {
+                text: String,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for CommentCreate {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field1_finish(
+                        f,
+                        "CommentCreate",
+                        "text",
+                        &&self.text,
+                    )
+                }
+            }
+            #[doc = " Generated from [`Comment`]."]
+            pub struct CommentView {
+                id: u64,
+                text: String,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for CommentView {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field2_finish(
+                        f,
+                        "CommentView",
+                        "id",
+                        &self.id,
+                        "text",
+                        &&self.text,
+                    )
+                }
+            }
         }
         "###);
     }
 
     #[test]
-    fn upsert() {
-        insta::assert_snapshot!(run_for_fixture("upsert"), @r###"
-        pub mod upsert {
-            use structout::generate;
-            struct NewFields {
-                foo: u32,
-                bar: i32,
-                baz: i64,
+    fn mixins() {
+        insta::assert_snapshot!(run_for_fixture("mixins"), @r###"
+        pub mod mixins {
+            use structout::{generate, template};
+            macro_rules! Timestamped {
+                ($ ($input : tt) *) =>
+                {
+                    :: structout :: generate!
+                    {
+                        mixin Timestamped { created_at: u64, updated_at: u64, } $
+                        ($input) *
+                    }
+                };
             }
-            struct OverriddenField {
-                foo: u64,
+            pub struct Article {
+                title: String,
+                id: u64,
+                created_at: u64,
+                updated_at: u64,
+                deleted: bool,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for Article {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field5_finish(
+                        f,
+                        "Article",
+                        "title",
+                        &self.title,
+                        "id",
+                        &self.id,
+                        "created_at",
+                        &self.created_at,
+                        "updated_at",
+                        &self.updated_at,
+                        "deleted",
+                        &&self.deleted,
+                    )
+                }
+            }
+            #[doc = " Generated from [`Article`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `id`, `created_at`, `updated_at`."]
+            pub struct ArticleDraft {
+                title: String,
+                deleted: bool,
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Debug for ArticleDraft {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::debug_struct_field2_finish(
+                        f,
+                        "ArticleDraft",
+                        "title",
+                        &self.title,
+                        "deleted",
+                        &&self.deleted,
+                    )
+                }
             }
-            struct Tupled(u64);
         }
-        
         "###);
     }
 
     #[test]
-    fn shared_attrs() {
-        insta::assert_snapshot!(run_for_fixture("shared_attrs"), @r###"
-        pub mod shared_attrs {
+    fn from_file() {
+        insta::assert_snapshot!(run_for_fixture("from_file"), @r###"
+        pub mod from_file {
             use structout::generate;
-            struct InheritsAttributes {
-                foo: u32,
+            pub struct Address;
+            #[automatically_derived]
+            impl ::core::fmt::Debug for Address {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    ::core::fmt::Formatter::write_str(f, "Address")
+                }
+            }
+            const _: &[u8] =
+                b"{\n  \"$schema\": \"http://json-schema.org/draft-07/schema#\",\n  \"title\": \"Human\",\n  \"type\": \"object\",\n  \"properties\": {\n    \"name\": { \"type\": \"string\", \"description\": \"The full name\" },\n    \"age\": { \"type\": \"integer\", \"format\": \"uint32\" },\n    \"height\": { \"type\": \"number\" },\n    \"nicknames\": { \"type\": \"array\", \"items\": { \"type\": \"string\" } },\n    \"email\": { \"type\": [\"string\", \"null\"] },\n    \"type\": { \"type\": \"string\" },\n    \"address\": { \"$ref\": \"#/definitions/Address\" }\n  },\n  \"required\": [\"name\", \"age\", \"height\", \"nicknames\", \"email\", \"type\"],\n  \"definitions\": {\n    \"Address\": { \"type\": \"object\" }\n  }\n}\n";
+            pub struct Human {
+                #[doc = " The full name"]
+                name: String,
+                age: u32,
+                height: f64,
+                nicknames: Vec<String>,
+                email: Option<String>,
+                r#type: String,
+                address: Option<Address>,
             }
             #[automatically_derived]
-            #[allow(unused_qualifications)]
-            impl ::core::fmt::Debug for InheritsAttributes {
+            impl ::core::fmt::Debug for Human {
+                #[inline]
                 fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-                    match *self {
-                        InheritsAttributes {
-                            foo: ref __self_0_0,
-                        } => {
-                            let mut debug_trait_builder = f.debug_struct("InheritsAttributes");
-                            let _ = debug_trait_builder.field("foo", &&(*__self_0_0));
-                            debug_trait_builder.finish()
-                        }
-                    }
+                    let names: &'static _ = &[
+                        "name",
+                        "age",
+                        "height",
+                        "nicknames",
+                        "email",
+                        "type",
+                        "address",
+                    ];
+                    let values: &[&dyn ::core::fmt::Debug] = &[
+                        &self.name,
+                        &self.age,
+                        &self.height,
+                        &self.nicknames,
+                        &self.email,
+                        &self.r#type,
+                        &&self.address,
+                    ];
+                    ::core::fmt::Formatter::debug_struct_fields_finish(f, "Human", names, values)
                 }
             }
-            struct InheritsAttributesTwo {
-                foo: u32,
+            #[doc = " Generated from [`Human`]."]
+            #[doc = ""]
+            #[doc = " - Omitted fields: `age`, `height`, `nicknames`, `type`."]
+            pub struct HumanContact {
+                #[doc = " The full name"]
+                name: String,
+                email: Option<String>,
+                address: Option<Address>,
             }
             #[automatically_derived]
-            #[allow(unused_qualifications)]
-            impl ::core::fmt::Debug for InheritsAttributesTwo {
+            impl ::core::fmt::Debug for HumanContact {
+                #[inline]
                 fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-                    match *self {
-                        InheritsAttributesTwo {
-                            foo: ref __self_0_0,
-                        } => {
-                            let mut debug_trait_builder = f.debug_struct("InheritsAttributesTwo");
-                            let _ = debug_trait_builder.field("foo", &&(*__self_0_0));
-                            debug_trait_builder.finish()
-                        }
-                    }
+                    ::core::fmt::Formatter::debug_struct_field3_finish(
+                        f,
+                        "HumanContact",
+                        "name",
+                        &self.name,
+                        "email",
+                        &self.email,
+                        "address",
+                        &&self.address,
+                    )
                 }
             }
         }
@@ -0,0 +1,15 @@
+use structout::generate;
+
+generate!(
+  #[derive(Debug)]
+  #[structout(any(AnyHuman))]
+  pub struct Human<T> where T: Clone {
+    name: String,
+    surname: String,
+    extra: T,
+  } => {
+    HumanEditableParts => [omit(extra)],
+    HumanRow => [omit(surname), as_tuple()],
+    HumanNickname => [include(name), upsert(surname: Option<String>)],
+  }
+);
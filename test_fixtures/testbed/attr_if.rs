@@ -0,0 +1,35 @@
+use structout::generate;
+
+generate!(
+  #[derive(Debug, Clone, PartialEq)]
+  pub struct Record {
+    id: u64,
+    name: String,
+    deleted: bool,
+  } => {
+    RecordKey => [
+      include(id),
+      attr_if(has(id), #[derive(Hash, Eq)]),
+      field_attr_if(not(has(name)), id, #[doc = "The key of the record"]),
+    ],
+    RecordName => [
+      include(name),
+      attr_if(any(has(id), has(deleted)), #[derive(Hash, Eq)]),
+      attr_if(all(has(name), not(has(deleted))), #[derive(PartialOrd)]),
+    ],
+    DeletedRecord => [
+      omit(name),
+      field_attr_if(has(id, deleted), deleted, #[doc = "Always true"]),
+    ],
+  }
+);
+
+generate!(
+  pub struct Pair(u32, String, bool) => {
+    PairTail => [
+      omit(0),
+      attr_if(has(2), #[derive(Debug)]),
+      field_attr_if(has(2), 2, #[doc = "The flag"]),
+    ],
+  }
+);
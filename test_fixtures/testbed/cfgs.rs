@@ -0,0 +1,17 @@
+use structout::generate;
+
+generate!(
+  #[derive(Debug)]
+  pub struct Account {
+    id: u64,
+    #[cfg(test)]
+    fixture: &'static str,
+    #[cfg_attr(all(), allow(dead_code))]
+    balance: i64,
+  } => {
+    AccountRow => [as_tuple()],
+    AccountField => [as_field_enum()],
+    AccountAudit => [cfg(debug_assertions), omit(balance)],
+    AccountFixture => [cfg(test), include(fixture)],
+  }
+);
@@ -0,0 +1,13 @@
+use structout::generate;
+
+generate!(
+  pub struct Human<T> where T: Clone {
+    name: String,
+    age: u32,
+    extra: T,
+  } => {
+    Child => [omit(age), common_trait(HumanLike, setters)],
+    Adult => [upsert(age: u64), common_trait(HumanLike, setters)],
+    Record => [omit(name), as_tuple(), common_trait(Aged)],
+  }
+);
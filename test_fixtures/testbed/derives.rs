@@ -0,0 +1,13 @@
+use structout::generate;
+
+generate!(
+  #[derive(Debug, Default)]
+  pub struct Settings {
+    volume: u8,
+    theme: String,
+  } => {
+    SettingsUpdate => [derive(Clone, Debug, PartialEq)],
+    SettingsPath => [upsert(theme: &'static std::path::Path), underive(Default)],
+    SettingsVolume => [include(volume), attr(#[derive(Clone, Copy)]), derive(Copy, Hash)],
+  }
+);
@@ -0,0 +1,25 @@
+use structout::generate;
+
+generate!(
+  /// A user of the service
+  pub struct User {
+    id: u64,
+    name: String,
+    password_hash: String,
+  } => {
+    UserView => [omit(password_hash), doc("What clients get to see of a user.")],
+    UserUpdate => [omit(id, password_hash), upsert(name: Option<String>, email: Option<String>)],
+    UserRow => [as_tuple()],
+  }
+);
+
+generate!(
+  #[structout(no_auto_doc)]
+  pub struct Session {
+    token: String,
+    user_id: u64,
+  } => {
+    SessionToken => [include(token), doc("Only the token of a session.")],
+    SessionUser => [include(user_id)],
+  }
+);
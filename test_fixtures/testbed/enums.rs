@@ -0,0 +1,14 @@
+use structout::generate;
+
+generate!(
+  pub enum Event<T> where T: Clone {
+    Connected { id: u32 },
+    Message(T),
+    #[structout(tag = "internal")]
+    Heartbeat,
+  } => {
+    ClientEvent => [omit(Heartbeat)],
+    ServerEvent => [omit_tagged(internal), upsert(Disconnected(u32))],
+    TextEvent => [omit_type(T)],
+  }
+);
@@ -0,0 +1,15 @@
+use structout::generate;
+
+generate!(
+  pub struct User {
+    #[doc = "The id of the user"]
+    id: u64,
+    #[allow(dead_code)]
+    #[doc = "The name of the user"]
+    name: String,
+  } => {
+    UserDto => [field_attr(name, #[allow(unused)]), field_attr_all(#[allow(clippy::all)])],
+    UserRow => [strip_field_attrs(doc, allow)],
+    UserPatch => [strip_field_attrs(doc), field_attr(id, #[doc = "The id of the user to patch"])],
+  }
+);
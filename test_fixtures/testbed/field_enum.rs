@@ -0,0 +1,13 @@
+use structout::generate;
+
+generate!(
+  pub struct Human<T> where T: Clone {
+    name: String,
+    age: u32,
+    extra: T,
+  } => {
+    HumanField => [as_field_enum()],
+    HumanAttribute => [omit(extra), as_field_enum()],
+    HumanUpdate => [upsert(age: u64), as_field_enum()],
+  }
+);
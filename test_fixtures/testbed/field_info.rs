@@ -0,0 +1,15 @@
+use structout::generate;
+
+generate!(
+  #[derive(Debug)]
+  pub struct Human<T> where T: Clone {
+    #[allow(dead_code)]
+    name: String,
+    age: u32,
+    extra: Vec<T>,
+  } => {
+    HumanInfo => [omit(extra), field_info()],
+    HumanRow => [as_tuple(), field_info()],
+    HumanId => [include(name)],
+  }
+);
@@ -0,0 +1,25 @@
+use structout::generate;
+
+generate!(
+  #[structout(field_matrix, no_auto_doc)]
+  /// An account of the service
+  pub struct Account {
+    id: u64,
+    email: String,
+    password_hash: String,
+  } => {
+    AccountView => [omit(password_hash)],
+    AccountSignup => [omit(id), upsert(password: String)],
+  }
+);
+
+generate!(
+  #[structout(field_matrix(token_fields), no_auto_doc)]
+  pub {
+    token: String,
+    expires_at: u64,
+  } => {
+    Token => [],
+    TokenValue => [include(token), as_tuple()],
+  }
+);
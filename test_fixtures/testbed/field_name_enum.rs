@@ -0,0 +1,14 @@
+use structout::generate;
+
+generate!(
+  #[derive(Debug, Clone, PartialEq)]
+  pub struct Account<T> where T: Clone {
+    id: u64,
+    r#type: String,
+    owner: T,
+  } => {
+    AccountField => [as_field_name_enum()],
+    NewAccount => [omit(id)],
+    AccountRow => [omit(owner), as_tuple()],
+  }
+);
@@ -0,0 +1,11 @@
+use structout::generate;
+
+#[derive(Debug)]
+pub struct Address;
+
+generate!(
+  #[derive(Debug)]
+  pub struct Human from_file = "schemas/human.json" => {
+    HumanContact => [include(name, email, address)],
+  }
+);
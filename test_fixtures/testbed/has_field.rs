@@ -0,0 +1,17 @@
+use structout::generate;
+
+generate!(
+  #[structout(has_field)]
+  pub struct Human<T> where T: Clone {
+    name: String,
+    age: u32,
+    extra: T,
+  } => {
+    HumanName => [include(name)],
+    HumanRow => [omit(extra), as_tuple()],
+  }
+);
+
+pub fn greet<T: structout_runtime::HasField<fields::name, Type = String>>(human: &T) -> String {
+  format!("Hello, {}", human.get())
+}
@@ -0,0 +1,31 @@
+use structout::generate;
+
+generate!(
+  pub struct Human<T> where T: Clone {
+    name: String,
+    surname: String,
+    extra: T,
+  } => {
+    HumanName => [omit(extra)],
+    HumanExtra => [include(extra)],
+    HumanRow => [as_tuple()],
+  }
+  impl {
+    pub fn full_name(&self) -> String {
+      [self.name.as_str(), self.surname.as_str()].join(" ")
+    }
+
+    pub fn extra(&self) -> &T {
+      &self.extra
+    }
+
+    pub fn repeat_extra(&self, count: usize) -> Vec<T> {
+      vec![self.extra.clone(); count]
+    }
+  }
+  impl for HumanRow {
+    pub fn first(&self) -> &String {
+      &self.0
+    }
+  }
+);
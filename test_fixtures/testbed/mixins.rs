@@ -0,0 +1,25 @@
+use structout::{generate, template};
+
+template!(
+  mixin Timestamped {
+    created_at: u64,
+    updated_at: u64,
+  }
+);
+
+generate!(
+  use Timestamped;
+
+  mixin Identified {
+    id: u64,
+  }
+
+  #[derive(Debug)]
+  pub struct Article {
+    title: String,
+  } + mixin(Identified, Timestamped) + {
+    deleted: bool,
+  } => {
+    ArticleDraft => [omit(id, created_at, updated_at)],
+  }
+);
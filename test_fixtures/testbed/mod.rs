@@ -1,8 +1,32 @@
+pub mod any;
 pub mod as_tuple;
+pub mod attr_if;
+pub mod cfgs;
+pub mod common_trait;
+pub mod derives;
+pub mod docs;
+pub mod enums;
+pub mod field_attrs;
+pub mod field_enum;
+pub mod field_info;
+pub mod field_matrix;
+pub mod field_name_enum;
+pub mod from_file;
 pub mod generics;
+pub mod has_field;
+pub mod impl_templates;
 pub mod include;
+pub mod mixins;
+pub mod presets;
+pub mod select;
 pub mod shared_attrs;
 pub mod simple;
+pub mod strip_attrs;
+pub mod templates;
+pub mod tuple_base;
+pub mod tuple_conversions;
+pub mod unit;
 pub mod upsert;
 pub mod visibility;
+pub mod visit_fields;
 pub mod wheres;
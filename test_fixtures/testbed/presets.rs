@@ -0,0 +1,25 @@
+use structout::generate;
+
+generate!(
+  preset Crud {
+    "{Base}Create" => [omit(id)],
+    "{Base}View" => [],
+  }
+
+  #[derive(Debug)]
+  pub struct User {
+    id: u64,
+    name: String,
+  } => {
+    ..Crud,
+    "{Base}Admin" => [include(id)],
+  }
+
+  #[derive(Debug)]
+  pub struct Post {
+    id: u64,
+    title: String,
+  } => {
+    ..Crud,
+  }
+);
@@ -0,0 +1,19 @@
+use structout::generate;
+
+struct Secret<T>(T);
+
+generate!(
+  <T> {
+    id: u32,
+    #[structout(tag = "internal")]
+    revision: u64,
+    password: Secret<String>,
+    nickname: Option<String>,
+    parent: Option<T>,
+  } => {
+    WithoutSecrets => [omit_type(Secret<_>)],
+    OnlyOptionals => [include_type(Option<_>)],
+    WithoutInternals => [omit_tagged(internal), omit(password)],
+    OnlyInternals => [include_tagged(internal)],
+  }
+);
@@ -0,0 +1,16 @@
+use structout::generate;
+
+generate!(
+  #[derive(Debug, Default, Clone)]
+  #[allow(dead_code, missing_docs)]
+  #[doc = "A user"]
+  pub struct User {
+    id: u64,
+    name: String,
+  } => {
+    UserRef => [upsert(name: &'static std::path::Path), strip_attr(derive(Default))],
+    UserId => [include(id), strip_attr(allow(missing_docs), doc)],
+    UserName => [include(name), no_inherit(), attr(#[derive(Debug)])],
+    UserKey => [include(id), strip_attr(derive(Debug, Default, Clone), doc = "A user")],
+  }
+);
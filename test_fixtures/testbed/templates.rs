@@ -0,0 +1,21 @@
+use structout::{generate, template};
+
+template!(
+  /// Outputs for creating and viewing an entity
+  preset Crud {
+    "{Base}Create" => [omit(id)],
+    "{Base}View" => [],
+  }
+);
+
+generate!(
+  use Crud;
+
+  #[derive(Debug)]
+  pub struct Comment {
+    id: u64,
+    text: String,
+  } => {
+    ..Crud,
+  }
+);
@@ -0,0 +1,10 @@
+use structout::generate;
+
+generate!(
+  <T> (u32, String, #[allow(dead_code)] T) where T: Copy => {
+    WithoutFirst => [omit(0)],
+    OnlyLast => [include(1, 2)],
+    Replaced => [upsert(1: &'static str, 3: bool)],
+    Named => [omit(1), as_named(id, value)],
+  }
+);
@@ -0,0 +1,12 @@
+use structout::generate;
+
+generate!(
+  <T> where T: Clone {
+    pub foo: u32,
+    bar: T,
+  } => {
+    Tupled => [as_tuple()],
+    Named => [],
+    OnlyBar => [omit(foo), as_tuple()],
+  }
+);
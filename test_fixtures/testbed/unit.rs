@@ -0,0 +1,11 @@
+use structout::generate;
+
+generate!(
+  <T> where T: Copy {
+    foo: T,
+  } => {
+    Nothing => [omit(foo)],
+    NothingTupled => [omit(foo), as_tuple()],
+    Marker => [as_unit()],
+  }
+);
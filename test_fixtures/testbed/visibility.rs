@@ -3,7 +3,12 @@ use structout::generate;
 generate!(
   pub(crate) {
     foo: u32,
+    pub(crate) bar: u64,
   } => {
     Everything => [],
+    Private => [vis()],
+    Public => [vis(pub), field_vis(pub)],
+    OnlyFooPublic => [field_vis(pub, foo)],
+    Tupled => [as_tuple(), field_vis(pub(super), foo)],
   }
 );
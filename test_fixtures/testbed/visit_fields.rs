@@ -0,0 +1,14 @@
+use structout::generate;
+
+generate!(
+  #[derive(Debug)]
+  pub struct Human<T> where T: Clone {
+    name: String,
+    age: u32,
+    extra: Vec<T>,
+  } => {
+    HumanVisited => [visit_fields()],
+    HumanRow => [omit(age), as_tuple(), visit_fields()],
+    HumanMarker => [as_unit(), visit_fields()],
+  }
+);